@@ -1,6 +1,11 @@
 use core::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
 use bitcoin::Network;
+use clap::Parser;
 use iced::Alignment::Center;
 use iced::Element;
 use iced::Event;
@@ -22,6 +27,7 @@ use iced::widget::column;
 use iced::widget::container;
 use iced::widget::image;
 use iced::widget::row;
+use iced::widget::stack;
 use iced::widget::text;
 use iced::window;
 use iced::window::Icon;
@@ -30,11 +36,12 @@ use iced::window::Position;
 use iced::window::Settings;
 use iced::window::icon;
 use iced::window::settings::PlatformSpecific;
-use tokio::runtime::Handle;
 use tracing::error;
 use tracing::info;
 
 use crate::about::view::view_about;
+use crate::common::diagnostics::DIAGNOSTICS_DIR;
+use crate::common::diagnostics::Diagnostics;
 use crate::common::interface::button::sidebar_button;
 use crate::common::interface::color::DARK_GREY;
 use crate::common::interface::color::GREEN_SHAMROCK;
@@ -58,28 +65,36 @@ use crate::common::interface::container::header_container;
 use crate::common::interface::container::sidebar_container;
 use crate::common::interface::font::BERKELEY_MONO_BOLD;
 use crate::common::interface::font::BERKELEY_MONO_REGULAR;
+use crate::common::interface::toast::Toast;
+use crate::common::interface::toast::push_toast;
+use crate::common::interface::toast::view_toasts;
+use crate::common::log_file::LogFiles;
+use crate::common::logger::init_log_levels;
 use crate::common::logger::setup_logger;
+use crate::common::tab::Tab;
 use crate::common::util::format_thousands;
 use crate::node::control::EmbeddedNode;
-use crate::node::control::NETWORK;
 use crate::node::control::NodeStatus;
-use crate::node::control::start_node;
-use crate::node::control::stop_node;
-use crate::node::error::BonsaiNodeError;
+use crate::node::control::Supervisor;
+use crate::node::events::EVENT_SOCKET;
+use crate::node::events::Event;
 use crate::node::geoip::GeoIpReader;
+use crate::node::lock::DataDirLock;
 use crate::node::message::NodeMessage;
+use crate::node::shutdown::WeakNode;
 use crate::node::style::table_cell;
-use crate::settings::bonsai_settings::AUTO_START_NODE;
 use crate::settings::bonsai_settings::BonsaiSettings;
 use crate::settings::bonsai_settings::BonsaiSettingsMessage;
-use crate::settings::bonsai_settings::SETTINGS_FILE;
+use crate::settings::cli::Cli;
+use crate::wallet::electrum;
 use crate::wallet::placeholder::Wallet;
 use crate::wallet::placeholder::WalletMessage;
 
+pub(crate) use bonsai_wallet::common;
+pub(crate) use bonsai_wallet::node;
+pub(crate) use bonsai_wallet::settings;
+
 pub(crate) mod about;
-pub(crate) mod common;
-pub(crate) mod node;
-pub(crate) mod settings;
 pub(crate) mod wallet;
 
 pub(crate) const APP_VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
@@ -90,18 +105,6 @@ pub(crate) const FLORESTA_ICON_PATH: &str = "./assets/icon/floresta.png";
 pub(crate) const BDK_ICON_PATH: &str = "./assets/icon/bdk.png";
 //pub(crate) const BONSAI_ICON_LIGHT_PATH: &str = "./assets/icon/bonsai-light.png";
 
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
-pub(crate) enum Tab {
-    Wallet,
-    #[default]
-    NodeMetrics,
-    NodeNetwork,
-    NodeBlocks,
-    NodeUtreexo,
-    Settings,
-    About,
-}
-
 #[derive(Debug, Clone)]
 pub(crate) enum BonsaiMessage {
     AnimationTick,
@@ -113,6 +116,7 @@ pub(crate) enum BonsaiMessage {
     Settings(BonsaiSettingsMessage),
     Node(NodeMessage),
     BdkWallet(WalletMessage),
+    DismissToast(usize),
 }
 
 pub(crate) struct Bonsai {
@@ -122,6 +126,11 @@ pub(crate) struct Bonsai {
     pub(crate) node: EmbeddedNode,
     pub(crate) wallet: Wallet,
     pub(crate) settings: BonsaiSettings,
+    pub(crate) toasts: Vec<Toast>,
+    /// Follows the node to the active network's data directory.
+    pub(crate) log_files: LogFiles,
+    /// Set while the Electrum server runs, so it is only started once per node.
+    pub(crate) electrum_serving: Arc<AtomicBool>,
 }
 
 impl Default for Bonsai {
@@ -133,6 +142,9 @@ impl Default for Bonsai {
             node: EmbeddedNode::default(),
             wallet: Wallet::default(),
             settings: BonsaiSettings::default(),
+            toasts: Vec::new(),
+            log_files: LogFiles::default(),
+            electrum_serving: Arc::default(),
         }
     }
 }
@@ -140,16 +152,33 @@ impl Default for Bonsai {
 impl Bonsai {
     fn view(&self) -> Element<'_, BonsaiMessage> {
         let node_status = &self.node.status;
-        let status_color = match node_status {
-            NodeStatus::Starting => pulse_color(GREEN_SHAMROCK, self.app_clock),
-            NodeStatus::Running => GREEN_SHAMROCK,
-            NodeStatus::Inactive => OFF_WHITE,
-            NodeStatus::ShuttingDown => pulse_color(RED, self.app_clock),
-            NodeStatus::Failed(_) => RED,
-        };
+        let status_color = node_status.color(self.app_clock);
         let blocks = self.node.statistics.as_ref().map(|s| s.blocks).unwrap_or(0);
         let network_color = network_color(&self.active_network);
 
+        // Wallet balance and sync status, relative to the node's validation height.
+        let balance = self.wallet.balance();
+        let confirmed = balance
+            .as_ref()
+            .map(|b| format!("{} SATS", format_thousands(b.confirmed.to_sat())))
+            .unwrap_or("NULL".to_string());
+        let unconfirmed = balance
+            .as_ref()
+            .map(|b| {
+                let pending = b.trusted_pending + b.untrusted_pending;
+                format!("{} SATS", format_thousands(pending.to_sat()))
+            })
+            .unwrap_or("NULL".to_string());
+        let wallet_height = self.wallet.synced_height();
+        let wallet_sync = wallet_height
+            .map(|h| format!("{} / {}", format_thousands(h), format_thousands(blocks)))
+            .unwrap_or("NULL".to_string());
+        let (wallet_status, wallet_status_color) = match wallet_height {
+            None => ("NO WALLET", OFF_WHITE),
+            Some(height) if height < blocks => ("SYNCING", pulse_color(ORANGE, self.app_clock)),
+            Some(_) => ("SYNCED", GREEN_SHAMROCK),
+        };
+
         let header = container(
             container(
                 row![
@@ -181,6 +210,26 @@ impl Bonsai {
                     Space::new().width(Length::Fill),
                     // Right.
                     row![
+                        column![
+                            text(wallet_status)
+                                .size(12)
+                                .font(BERKELEY_MONO_BOLD)
+                                .color(wallet_status_color),
+                            text(confirmed).size(12).font(BERKELEY_MONO_BOLD),
+                            text(unconfirmed).size(12).font(BERKELEY_MONO_BOLD),
+                            text(wallet_sync).size(12).font(BERKELEY_MONO_BOLD),
+                        ]
+                        .spacing(2)
+                        .align_x(iced::Alignment::End),
+                        column![
+                            text("WALLET").size(12),
+                            text("BALANCE").size(12),
+                            text("PENDING").size(12),
+                            text("SYNC").size(12),
+                        ]
+                        .spacing(2)
+                        .align_x(iced::Alignment::Start),
+                        Space::new().width(Length::Fixed(20.0)),
                         column![
                             text(node_status.to_string())
                                 .size(12)
@@ -317,11 +366,16 @@ impl Bonsai {
             .width(Length::Fill)
             .height(Length::Fill);
 
-        container(inner)
+        let window = container(inner)
             .padding(WINDOW_PADDING)
             .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+            .height(Length::Fill);
+
+        stack![
+            window,
+            view_toasts(&self.toasts, BonsaiMessage::DismissToast)
+        ]
+        .into()
     }
 
     fn update(&mut self, message: BonsaiMessage) -> Task<BonsaiMessage> {
@@ -383,34 +437,62 @@ impl Bonsai {
             }
             BonsaiMessage::AnimationTick => {
                 self.app_clock = self.app_clock.wrapping_add(1);
+                self.toasts
+                    .retain(|toast| toast.expires_at != self.app_clock);
                 Task::none()
             }
-            BonsaiMessage::BdkWallet(msg) => {
-                self.wallet.update(msg);
+            BonsaiMessage::DismissToast(idx) => {
+                if idx < self.toasts.len() {
+                    self.toasts.remove(idx);
+                }
                 Task::none()
             }
+            BonsaiMessage::BdkWallet(msg) => {
+                match &msg {
+                    WalletMessage::ExportBackup => {
+                        // The backup bundles the application settings.
+                        return self
+                            .wallet
+                            .export_backup(&self.settings)
+                            .map(BonsaiMessage::BdkWallet);
+                    }
+                    WalletMessage::PlanConsolidation => {
+                        // The plan is priced with the node's recent feerates.
+                        self.wallet.plan_consolidation(&self.node.fee_estimator);
+                        return Task::none();
+                    }
+                    WalletMessage::SettingsRestored(network, settings) => {
+                        // Restore the settings bundled in the backup, once its wallet is restored.
+                        let network_changed = self.active_network != *network;
+                        self.settings = (**settings).clone();
+                        self.settings.bonsai.network = Some(*network);
+                        self.settings.node_restart_required = network_changed;
+                        self.active_network = *network;
+                        if let Err(e) = self.settings.save() {
+                            error!("Failed to save restored settings: {}", e);
+                        }
+                    }
+                    _ => {}
+                }
+
+                let task = self.wallet.update(msg).map(BonsaiMessage::BdkWallet);
+                Task::batch([
+                    task,
+                    self.start_wallet_rescan(),
+                    self.start_electrum_server(),
+                ])
+            }
             BonsaiMessage::CloseRequested => {
                 if let Err(e) = self.settings.save() {
                     eprintln!("Failed to save settings on close: {}", e);
                 }
 
                 if self.node.handle.is_some() {
-                    let stopping_task = Task::done(BonsaiMessage::Node(NodeMessage::ShuttingDown));
-                    self.node.unsubscribe();
-
-                    // Take the handle for shutdown
-                    let node_handle = self.node.handle.take().unwrap();
-                    let rt_handle = Handle::current();
-
-                    let shutdown_task = Task::future(async move {
-                        let _ = rt_handle
-                            .spawn(async move { stop_node(node_handle).await })
-                            .await;
-
-                        BonsaiMessage::CloseWindow
-                    });
-
-                    Task::batch([stopping_task, shutdown_task])
+                    // Close the window once the node is shut down, even if it fails to.
+                    self.node
+                        .begin_shutdown(false)
+                        .map(BonsaiMessage::Node)
+                        .chain(Task::done(BonsaiMessage::CloseWindow))
                 } else {
                     Task::done(BonsaiMessage::CloseWindow)
                 }
@@ -419,6 +501,8 @@ impl Bonsai {
                 .and_then(window::close::<BonsaiMessage>)
                 .discard(),
             BonsaiMessage::Node(msg) => {
+                let node_running = matches!(msg, NodeMessage::Running(..));
+
                 // Save settings when node shuts down or restarts
                 match &msg {
                     NodeMessage::Shutdown | NodeMessage::Restart => {
@@ -426,6 +510,75 @@ impl Bonsai {
                             eprintln!("Failed to save settings: {}", e);
                         }
                     }
+                    NodeMessage::NewBlock(block) => {
+                        // Keep the wallet in sync with blocks validated by the node.
+                        if let Ok(height) = block.bip34_block_height() {
+                            let height = height as u32;
+                            let events =
+                                self.wallet.apply_block(block, height).unwrap_or_else(|e| {
+                                    error!("{e}");
+                                    Vec::new()
+                                });
+                            let tip = self.wallet.synced_height().unwrap_or(height);
+
+                            // Notify about wallet transactions in the block.
+                            for event in events {
+                                self.node.events.publish(Event::wallet_tx(
+                                    event.txid,
+                                    height,
+                                    event.net(),
+                                ));
+
+                                let (title, sign, color) = if event.is_incoming() {
+                                    ("INCOMING FUNDS", "+", GREEN_SHAMROCK)
+                                } else {
+                                    ("OUTGOING FUNDS", "", ORANGE)
+                                };
+                                let confirmations = event.confirmations(tip);
+                                let body = format!(
+                                    "{sign}{} SATS IN BLOCK {}\n{} CONFIRMATION{}",
+                                    format_thousands(event.net().to_sat()),
+                                    format_thousands(height),
+                                    confirmations,
+                                    if confirmations == 1 { "" } else { "S" },
+                                );
+                                push_toast(
+                                    &mut self.toasts,
+                                    Toast::new(title.to_string(), body, color, self.app_clock),
+                                );
+                            }
+                        }
+                    }
+                    NodeMessage::ExportDiagnostics => {
+                        let diagnostics = Diagnostics::collect(&self.settings, &self.node);
+                        let dir = self.settings.data_dir().join(DIAGNOSTICS_DIR);
+                        return Task::perform(diagnostics.export(dir), |result| {
+                            BonsaiMessage::Node(NodeMessage::DiagnosticsExported(result))
+                        });
+                    }
+                    NodeMessage::DiagnosticsExported(result) => {
+                        let toast = match result {
+                            Ok(path) => {
+                                info!("Exported diagnostics to {}", path.to_string_lossy());
+                                Toast::new(
+                                    "DIAGNOSTICS EXPORTED".to_string(),
+                                    path.to_string_lossy().to_string(),
+                                    GREEN_SHAMROCK,
+                                    self.app_clock,
+                                )
+                            }
+                            Err(e) => {
+                                error!("Failed to export diagnostics: {e}");
+                                Toast::new(
+                                    "EXPORT FAILED".to_string(),
+                                    e.clone(),
+                                    RED,
+                                    self.app_clock,
+                                )
+                            }
+                        };
+                        push_toast(&mut self.toasts, toast);
+                    }
                     NodeMessage::ConfigUsed(config) => {
                         // Update settings with the actual config used by the node
                         self.settings.update_from_config(config);
@@ -436,7 +589,17 @@ impl Bonsai {
                     _ => {}
                 }
 
-                self.node.update(msg).map(BonsaiMessage::Node)
+                let task = self.node.update(msg).map(BonsaiMessage::Node);
+
+                if node_running {
+                    Task::batch([
+                        task,
+                        self.start_wallet_rescan(),
+                        self.start_electrum_server(),
+                    ])
+                } else {
+                    task
+                }
             }
             BonsaiMessage::Settings(msg) => {
                 // Check if it's a restart request before updating
@@ -445,15 +608,32 @@ impl Bonsai {
                 let task = self.settings.update(msg).map(BonsaiMessage::Settings);
 
                 // Sync `active_network` with settings after any settings update
-                self.active_network = self.settings.bonsai.network.unwrap_or(Network::Signet);
+                self.active_network = self.settings.network();
+                self.node.supervisor.configure(
+                    self.settings.restart_policy(),
+                    self.settings.max_restarts(),
+                    self.settings.stall_timeout(),
+                );
 
                 if should_restart {
                     // Update the node config before restarting
-                    let network = self.settings.bonsai.network.unwrap_or(NETWORK);
+                    let network = self.settings.network();
                     let node_config = self
                         .settings
                         .get_node_config(network, &BonsaiSettings::base_dir());
                     self.node.config = Some(node_config);
+                    self.node.rpc_port = self.settings.rpc_port();
+                    self.node.rest_address = self.settings.node.rest_address;
+                    self.node.metrics_port = self.settings.metrics_port();
+                    self.node.event_socket =
+                        self.settings.node.event_socket.unwrap_or(EVENT_SOCKET);
+
+                    // Logs and wallets are per-network.
+                    self.log_files
+                        .open(&self.settings.data_dir(), self.settings.log_rotation());
+                    if self.wallet.network != network {
+                        self.wallet = Wallet::load(network);
+                    }
 
                     // Trigger node restart
                     let restart_task = Task::done(BonsaiMessage::Node(NodeMessage::Restart));
@@ -465,6 +645,58 @@ impl Bonsai {
         }
     }
 
+    /// Start the wallet's pending rescan, if the node is running on the wallet's network.
+    fn start_wallet_rescan(&mut self) -> Task<BonsaiMessage> {
+        let node_network = self.node.config.as_ref().map(|config| config.network);
+
+        match &self.node.handle {
+            Some(handle)
+                if matches!(self.node.status, NodeStatus::Running)
+                    && node_network == Some(self.wallet.network) =>
+            {
+                self.wallet
+                    .start_rescan(handle.clone(), self.node.canceller.token())
+                    .map(BonsaiMessage::BdkWallet)
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Serve the wallet's scripts over Electrum, if enabled and the node
+    /// is running on the wallet's network.
+    ///
+    /// The server shares the wallet's [`ElectrumIndex`](crate::wallet::electrum::ElectrumIndex),
+    /// so once it runs, it serves whatever wallet is loaded or restored later on.
+    fn start_electrum_server(&self) -> Task<BonsaiMessage> {
+        let node_network = self.node.config.as_ref().map(|config| config.network);
+
+        match (&self.node.handle, self.settings.electrum_address()) {
+            (Some(handle), Some(address))
+                if matches!(self.node.status, NodeStatus::Running)
+                    && node_network == Some(self.wallet.network)
+                    && !self.electrum_serving.swap(true, Ordering::Relaxed) =>
+            {
+                let server = electrum::serve(
+                    WeakNode::new(handle, self.node.canceller.token()),
+                    self.wallet.electrum_index.clone(),
+                    self.node.fee_estimator.clone(),
+                    self.wallet.network,
+                    address,
+                    self.node.canceller.token(),
+                );
+                let serving = self.electrum_serving.clone();
+                Task::future(async move {
+                    if let Err(e) = server.await {
+                        error!("Failed to start Electrum server: {e}");
+                    }
+                    serving.store(false, Ordering::Relaxed);
+                })
+                .discard()
+            }
+            _ => Task::none(),
+        }
+    }
+
     fn subscription(&self) -> Subscription<BonsaiMessage> {
         let animation_timer =
             time::every(std::time::Duration::from_millis(32)).map(|_| BonsaiMessage::AnimationTick);
@@ -485,12 +717,15 @@ impl Bonsai {
             }
         });
 
+        // Node events are processed in the background, whatever tab is visible.
+        let node_subscription = self.node.subscribe().map(BonsaiMessage::Node);
+
         let tab_subscription = match self.active_tab {
             Tab::Wallet => Subscription::none(),
-            Tab::NodeMetrics => self.node.subscribe().map(BonsaiMessage::Node),
-            Tab::NodeNetwork => self.node.subscribe().map(BonsaiMessage::Node),
-            Tab::NodeBlocks => self.node.subscribe().map(BonsaiMessage::Node),
-            Tab::NodeUtreexo => self.node.subscribe().map(BonsaiMessage::Node),
+            Tab::NodeMetrics => self.node.view_subscription().map(BonsaiMessage::Node),
+            Tab::NodeNetwork => self.node.view_subscription().map(BonsaiMessage::Node),
+            Tab::NodeBlocks => self.node.view_subscription().map(BonsaiMessage::Node),
+            Tab::NodeUtreexo => self.node.view_subscription().map(BonsaiMessage::Node),
             Tab::Settings => Subscription::none(),
             Tab::About => Subscription::none(),
         };
@@ -499,14 +734,19 @@ impl Bonsai {
             animation_timer,
             window_events,
             keyboard_events,
+            node_subscription,
             tab_subscription,
         ])
     }
 }
 
 fn main() -> iced::Result {
+    // Parse the command-line arguments.
+    let cli = Cli::parse();
+    cli.set_paths();
+
     // Setup the logger.
-    let log_capture = setup_logger();
+    let (log_capture, log_files) = setup_logger(cli.log_level.as_deref());
 
     // Create a Tokio runtime for the underlying node to run on.
     let rt = tokio::runtime::Builder::new_multi_thread()
@@ -547,13 +787,13 @@ fn main() -> iced::Result {
     let mut settings = BonsaiSettings::load();
 
     // Check if this is the first run by seeing if the file exists
-    let settings_file = BonsaiSettings::base_dir().join(SETTINGS_FILE);
+    let settings_file = BonsaiSettings::path();
     let is_first_run = !settings_file.exists();
 
     // On first run, populate settings with the actual config that will be used
     // and save it to disk
     if is_first_run {
-        let network = settings.bonsai.network.unwrap_or(Network::Signet);
+        let network = settings.network();
 
         let node_config = settings.get_node_config(network, &BonsaiSettings::base_dir());
         settings.update_from_config(&node_config);
@@ -575,9 +815,21 @@ fn main() -> iced::Result {
         }
     }
 
-    let auto_start_node = settings.node.auto_start.unwrap_or(AUTO_START_NODE);
-    let network = settings.bonsai.network.unwrap_or(Network::Signet);
+    // Apply the command-line overrides, which are never saved.
+    settings.apply_overrides(cli.overrides());
+
+    let network = settings.network();
     let node_config = settings.get_node_config(network, &BonsaiSettings::base_dir());
+    log_files.open(&settings.data_dir(), settings.log_rotation());
+    init_log_levels(&settings.bonsai.log_levels);
+
+    // Another instance on the same data directory is reported instead of starting a second node.
+    let instance_conflict = DataDirLock::check(Path::new(&node_config.datadir)).err();
+    if let Some(e) = &instance_conflict {
+        error!("{e}");
+    }
+    let auto_start_node = settings.auto_start_node() && instance_conflict.is_none();
+    let no_geoip = cli.no_geoip;
 
     iced::application(
         move || {
@@ -589,24 +841,42 @@ fn main() -> iced::Result {
                 node: EmbeddedNode {
                     config: Some(node_config.clone()),
                     log_capture: log_capture.clone(),
-                    geoip_reader: GeoIpReader::new(GEOIP_ASN_DB_PATH, GEOIP_CITY_DB_PATH).ok(),
+                    geoip_reader: if no_geoip {
+                        None
+                    } else {
+                        GeoIpReader::new(GEOIP_ASN_DB_PATH, GEOIP_CITY_DB_PATH).ok()
+                    },
                     block_explorer_height_str: String::from("0"),
+                    rpc_port: settings.rpc_port(),
+                    rest_address: settings.node.rest_address,
+                    metrics_port: settings.metrics_port(),
+                    event_socket: settings.node.event_socket.unwrap_or(EVENT_SOCKET),
+                    supervisor: Supervisor::new(
+                        settings.restart_policy(),
+                        settings.max_restarts(),
+                        settings.stall_timeout(),
+                    ),
                     ..EmbeddedNode::default()
                 },
-                wallet: Wallet::default(),
+                wallet: Wallet::load(network),
+                toasts: instance_conflict
+                    .iter()
+                    .map(|e| {
+                        Toast::new(
+                            "ALREADY RUNNING".to_string(),
+                            format!("{e}\nThe node won't start until it exits."),
+                            RED,
+                            0,
+                        )
+                    })
+                    .collect(),
+                log_files: log_files.clone(),
+                electrum_serving: Arc::default(),
             };
 
+            // Started like any other start, so it gets the node's fee estimator and events.
             let tasks = if auto_start_node {
-                let network = settings.bonsai.network.unwrap_or(Network::Signet);
-                let node_config = settings.get_node_config(network, &BonsaiSettings::base_dir());
-
-                Task::batch([
-                    Task::done(BonsaiMessage::Node(NodeMessage::Starting)),
-                    Task::perform(start_node(node_config), |result| match result {
-                        Ok(handle) => BonsaiMessage::Node(NodeMessage::Running(handle)),
-                        Err(e) => BonsaiMessage::Node(NodeMessage::Error(BonsaiNodeError::from(e))),
-                    }),
-                ])
+                Task::done(BonsaiMessage::Node(NodeMessage::Start))
             } else {
                 Task::none()
             };
@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+use std::pin::pin;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use bonsai_wallet::common::logger::init_log_levels;
+use bonsai_wallet::common::logger::setup_logger;
+use bonsai_wallet::node::control::start_node;
+use bonsai_wallet::node::error::BonsaiNodeError;
+use bonsai_wallet::node::events;
+use bonsai_wallet::node::events::EVENT_SOCKET;
+use bonsai_wallet::node::events::EVENT_SOCKET_FILE;
+use bonsai_wallet::node::events::EventPublisher;
+use bonsai_wallet::node::exporter;
+use bonsai_wallet::node::fee_estimator::FeeEstimator;
+use bonsai_wallet::node::message::NodeMessage;
+use bonsai_wallet::node::rest;
+use bonsai_wallet::node::rpc;
+use bonsai_wallet::node::shutdown::TaskCanceller;
+use bonsai_wallet::node::shutdown::WeakNode;
+use bonsai_wallet::node::shutdown::shutdown;
+use bonsai_wallet::settings::bonsai_settings::BonsaiSettings;
+use bonsai_wallet::settings::cli::Cli;
+use clap::Parser;
+use iced::futures::StreamExt;
+use tracing::debug;
+use tracing::error;
+use tracing::info;
+
+/// Run the embedded node without a GUI, from the same settings as `bonsai`.
+#[tokio::main]
+async fn main() -> ExitCode {
+    // Parse the command-line arguments.
+    let cli = Cli::parse();
+    cli.set_paths();
+
+    // Setup the logger.
+    let (_log_capture, log_files) = setup_logger(cli.log_level.as_deref());
+
+    // Load [`BonsaiSettings`] from disk, with the command-line overrides.
+    let mut settings = BonsaiSettings::load();
+    settings.apply_overrides(cli.overrides());
+    log_files.open(&settings.data_dir(), settings.log_rotation());
+    init_log_levels(&settings.bonsai.log_levels);
+    let settings_file = BonsaiSettings::path();
+    if !settings_file.exists() {
+        info!(
+            "No settings found at {}, using defaults",
+            settings_file.to_string_lossy()
+        );
+    }
+
+    let network = settings.network();
+    let node_config = settings.get_node_config(network, &BonsaiSettings::base_dir());
+    let datadir = PathBuf::from(&node_config.datadir);
+
+    info!("Starting bonsaid on {network}");
+    let publisher = EventPublisher::default();
+    let started = start_node(node_config, FeeEstimator::default(), publisher.clone()).await;
+    let (handle, block_receiver) = match started {
+        Ok(node) => node,
+        Err(e) => {
+            error!("Failed to start node: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let start_time = Instant::now();
+
+    // Drain the block channel, otherwise the node stalls once it fills up.
+    // The channel only closes if the node stopped.
+    let mut drain = tokio::spawn(async move {
+        while let Some(block) = block_receiver.recv().await {
+            debug!("New block {}", block.header.block_hash());
+        }
+    });
+
+    // Cancels the servers and the event socket on shutdown.
+    let canceller = TaskCanceller::default();
+    if let Some(port) = settings.rpc_port() {
+        spawn_server(
+            &canceller,
+            rpc::serve(
+                WeakNode::new(&handle, canceller.token()),
+                network,
+                port,
+                datadir.clone(),
+            ),
+        );
+    }
+    if let Some(address) = settings.node.rest_address {
+        spawn_server(
+            &canceller,
+            rest::serve(WeakNode::new(&handle, canceller.token()), network, address),
+        );
+    }
+    if let Some(port) = settings.metrics_port() {
+        spawn_server(
+            &canceller,
+            exporter::serve(
+                WeakNode::new(&handle, canceller.token()),
+                network,
+                port,
+                start_time,
+            ),
+        );
+    }
+    if settings.node.event_socket.unwrap_or(EVENT_SOCKET) {
+        let path = datadir.join(EVENT_SOCKET_FILE);
+        spawn_server(
+            &canceller,
+            events::serve(path, publisher, canceller.token()),
+        );
+    }
+
+    // Exit with a failure if the node stops on its own, so a service manager restarts it.
+    let stopped = tokio::select! {
+        _ = wait_for_signal() => false,
+        _ = &mut drain => {
+            error!("The node stopped unexpectedly");
+            true
+        }
+    };
+
+    let mut shutdown_stream = pin!(shutdown(handle, canceller));
+    while let Some(message) = shutdown_stream.next().await {
+        match message {
+            NodeMessage::ShutdownProgress(progress) => info!("Shutting down: {progress}"),
+            NodeMessage::ShutdownFinished(Ok(())) if !stopped => {
+                info!("Node shutdown complete");
+                return ExitCode::SUCCESS;
+            }
+            NodeMessage::ShutdownFinished(Ok(())) => return ExitCode::FAILURE,
+            NodeMessage::ShutdownFinished(Err(e)) => {
+                error!("Failed to shutdown node: {e}");
+                return ExitCode::FAILURE;
+            }
+            _ => {}
+        }
+    }
+
+    if stopped {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Run a server until the node shuts down, logging its failure.
+fn spawn_server(
+    canceller: &TaskCanceller,
+    server: impl Future<Output = Result<(), BonsaiNodeError>> + Send + 'static,
+) {
+    tokio::spawn(canceller.token().run(async move {
+        if let Err(e) = server.await {
+            error!("{e}");
+        }
+    }));
+}
+
+/// Wait for SIGINT, or SIGTERM on Unix.
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::SignalKind;
+        use tokio::signal::unix::signal;
+
+        let mut sigterm = match signal(SignalKind::terminate()) {
+            Ok(sigterm) => sigterm,
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {e}");
+                let _ = tokio::signal::ctrl_c().await;
+                return;
+            }
+        };
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => info!("Received SIGINT"),
+            _ = sigterm.recv() => info!("Received SIGTERM"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Received SIGINT");
+    }
+}
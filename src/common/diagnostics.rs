@@ -0,0 +1,238 @@
+use std::fs;
+use std::fs::File;
+use std::path::PathBuf;
+
+use bdk_floresta::ConnectionKind;
+use bdk_floresta::TransportProtocol;
+use bdk_floresta::UtreexoNodeConfig;
+use chrono::Local;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::node::control::EmbeddedNode;
+use crate::node::stats_fetcher::NodeStatistics;
+use crate::settings::bonsai_settings::BonsaiSettings;
+
+/// Where diagnostics bundles are written, in the active network's data directory.
+pub const DIAGNOSTICS_DIR: &str = "diagnostics";
+
+/// Stands in for settings that could identify the user.
+const REDACTED: &str = "REDACTED";
+
+/// A snapshot of the app's state, to attach to bug reports.
+///
+/// Taken on the UI thread, and written to a `.tar.gz` in the background.
+pub struct Diagnostics {
+    /// `bonsai.toml`, with the proxy and fixed peers redacted.
+    settings: String,
+    node_config: Option<Value>,
+    statistics: Option<Value>,
+    system: Value,
+    logs: String,
+}
+
+impl Diagnostics {
+    pub fn collect(settings: &BonsaiSettings, node: &EmbeddedNode) -> Self {
+        // The proxy, fixed and manual peers, as they may appear in free text.
+        let mut secrets = Vec::new();
+        let settings_toml = toml::Value::try_from(settings)
+            .map(|mut value| {
+                redact(&mut value, &mut secrets);
+                toml::to_string_pretty(&value).unwrap_or_default()
+            })
+            .unwrap_or_else(|e| format!("# Failed to serialize settings: {e}\n"));
+        if let Some(config) = &node.config {
+            secrets.extend(config.fixed_peer.clone());
+            secrets.extend(config.proxy.map(|proxy| proxy.to_string()));
+        }
+        if let Some(stats) = &node.statistics {
+            secrets.extend(
+                stats
+                    .peer_informations
+                    .iter()
+                    .filter(|peer| matches!(peer.connection_kind, ConnectionKind::Manual))
+                    .map(|peer| peer.socket.to_string()),
+            );
+        }
+
+        let logs = node
+            .log_capture
+            .read_since(0)
+            .iter()
+            .map(|log| mask(&format!("{log}\n"), &secrets))
+            .collect();
+
+        Diagnostics {
+            settings: settings_toml,
+            node_config: node.config.as_ref().map(config_json),
+            statistics: node.statistics.as_ref().map(statistics_json),
+            system: json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "time": Local::now().to_rfc3339(),
+                "network": settings.network().to_string(),
+                "node_status": node.status.to_string(),
+                "proxy_status": mask(&node.proxy_status.to_string(), &secrets),
+                "os": std::env::consts::OS,
+                "os_family": std::env::consts::FAMILY,
+                "os_release": os_release(),
+                "arch": std::env::consts::ARCH,
+            }),
+            logs,
+        }
+    }
+
+    /// Write the bundle to a new `.tar.gz` in `dir`, returning its path.
+    pub async fn export(self, dir: PathBuf) -> Result<PathBuf, String> {
+        tokio::task::spawn_blocking(move || self.write(dir))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())
+    }
+
+    fn write(self, dir: PathBuf) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(&dir)?;
+        let name = format!(
+            "bonsai-diagnostics-{}",
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(format!("{name}.tar.gz"));
+
+        let encoder = GzEncoder::new(File::create(&path)?, Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+
+        let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+        let mut entries = vec![
+            ("system.json", pretty(&self.system)),
+            ("bonsai.toml", self.settings),
+            ("logs.txt", self.logs),
+        ];
+        if let Some(node_config) = &self.node_config {
+            entries.push(("node_config.json", pretty(node_config)));
+        }
+        if let Some(statistics) = &self.statistics {
+            entries.push(("statistics.json", pretty(statistics)));
+        }
+
+        for (file, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(Local::now().timestamp() as u64);
+            archive.append_data(&mut header, format!("{name}/{file}"), contents.as_bytes())?;
+        }
+        archive.into_inner()?.finish()?;
+
+        Ok(path)
+    }
+}
+
+/// Replace the proxy and fixed peers of every network, keeping what was replaced in `secrets`.
+fn redact(value: &mut toml::Value, secrets: &mut Vec<String>) {
+    if let toml::Value::Table(table) = value {
+        for (key, value) in table.iter_mut() {
+            if key == "proxy" || key == "fixed_peer" {
+                if let toml::Value::String(secret) = value {
+                    secrets.push(secret.clone());
+                }
+                *value = toml::Value::String(REDACTED.to_string());
+            } else {
+                redact(value, secrets);
+            }
+        }
+    }
+}
+
+/// Replace every occurrence of the `secrets` in `text`.
+fn mask(text: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |text, secret| {
+            text.replace(secret.as_str(), REDACTED)
+        })
+}
+
+fn redacted<T>(setting: &Option<T>) -> Value {
+    setting
+        .as_ref()
+        .map(|_| json!(REDACTED))
+        .unwrap_or(Value::Null)
+}
+
+fn config_json(config: &UtreexoNodeConfig) -> Value {
+    json!({
+        "network": config.network.to_string(),
+        "datadir": config.datadir,
+        "assume_utreexo": config.assume_utreexo.is_some(),
+        "pow_fraud_proofs": config.pow_fraud_proofs,
+        "backfill": config.backfill,
+        "user_agent": config.user_agent,
+        "allow_v1_fallback": config.allow_v1_fallback,
+        "fixed_peer": redacted(&config.fixed_peer),
+        "max_banscore": config.max_banscore,
+        "max_outbound": config.max_outbound,
+        "max_inflight": config.max_inflight,
+        "disable_dns_seeds": config.disable_dns_seeds,
+        "proxy": redacted(&config.proxy),
+        "compact_filters": config.compact_filters,
+        "filter_start_height": config.filter_start_height,
+    })
+}
+
+fn statistics_json(stats: &NodeStatistics) -> Value {
+    let peers: Vec<Value> = stats
+        .peer_informations
+        .iter()
+        .map(|peer| {
+            json!({
+                "address": match peer.connection_kind {
+                    ConnectionKind::Manual => json!(REDACTED),
+                    _ => json!(peer.socket.to_string()),
+                },
+                "user_agent": peer.user_agent,
+                "implementation": peer.node_impl.to_string(),
+                "initial_height": peer.initial_height,
+                "connection_kind": match peer.connection_kind {
+                    ConnectionKind::Manual => "manual",
+                    ConnectionKind::Feeler => "feeler",
+                    _ => "regular",
+                },
+                "transport_protocol": match peer.transport_protocol {
+                    TransportProtocol::V1 => "v1",
+                    TransportProtocol::V2 => "v2",
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "in_ibd": stats.in_ibd,
+        "headers": stats.headers,
+        "blocks": stats.blocks,
+        "accumulator": {
+            "leaves": stats.accumulator.leaves,
+            "roots": stats
+                .accumulator
+                .roots
+                .iter()
+                .map(|root| root.to_string())
+                .collect::<Vec<_>>(),
+        },
+        "user_agent": stats.user_agent,
+        "uptime_seconds": stats.uptime.as_secs(),
+        "resident_memory_bytes": stats.memory,
+        "allocated_memory_bytes": stats.allocated_memory,
+        "peers": peers,
+    })
+}
+
+/// The operating system's name and version, where it can be read.
+fn os_release() -> Option<String> {
+    let os_release = fs::read_to_string("/etc/os-release").ok()?;
+    os_release
+        .lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|name| name.trim_matches('"').to_string())
+}
@@ -14,7 +14,7 @@ use crate::common::interface::color::OFF_WHITE;
 use crate::common::interface::constants::BORDER_RADIUS;
 use crate::common::interface::constants::BORDER_WIDTH;
 
-pub(crate) fn sidebar_button(
+pub fn sidebar_button(
     is_active: bool,
     hover_color: Color,
 ) -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
@@ -68,7 +68,7 @@ pub(crate) fn sidebar_button(
     }
 }
 
-pub(crate) fn image_button() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
+pub fn image_button() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
     move |_theme, _button_status| ButtonStyle {
         border: Border::default(),
         background: None,
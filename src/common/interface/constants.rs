@@ -1,22 +1,27 @@
 use iced::Length;
 
-pub(crate) const WINDOW_PADDING: f32 = 5.0;
+pub const WINDOW_PADDING: f32 = 5.0;
 
-pub(crate) const CONTENT_PADDING: f32 = 10.0;
-pub(crate) const CONTENT_SPACING: f32 = 8.0;
+pub const CONTENT_PADDING: f32 = 10.0;
+pub const CONTENT_SPACING: f32 = 8.0;
 
-pub(crate) const BORDER_WIDTH: f32 = 1.5;
-pub(crate) const BORDER_RADIUS: f32 = 0.0;
+pub const BORDER_WIDTH: f32 = 1.5;
+pub const BORDER_RADIUS: f32 = 0.0;
 
-pub(crate) const CELL_HEIGHT: Length = Length::Fixed(35.0);
-pub(crate) const TABLE_HEADER_FONT_SIZE: u32 = 16;
-pub(crate) const TABLE_CELL_FONT_SIZE: u32 = 12;
-pub(crate) const TABLE_CELL_ICON_SIZE: u32 = 24;
+pub const CELL_HEIGHT: Length = Length::Fixed(35.0);
+pub const TABLE_HEADER_FONT_SIZE: u32 = 16;
+pub const TABLE_CELL_FONT_SIZE: u32 = 12;
+pub const TABLE_CELL_ICON_SIZE: u32 = 24;
 
-pub(crate) const HEADER_HEIGHT: f32 = 90.0;
-pub(crate) const HEADER_PADDING: f32 = 10.0;
+pub const HEADER_HEIGHT: f32 = 90.0;
+pub const HEADER_PADDING: f32 = 10.0;
 
-pub(crate) const SIDEBAR_WIDTH: f32 = 200.0;
-pub(crate) const SIDEBAR_PADDING: f32 = 10.0;
-pub(crate) const SIDEBAR_BUTTON_HEIGHT: f32 = 45.0;
-pub(crate) const SIDEBAR_BUTTON_SPACING: f32 = 10.0;
+pub const SIDEBAR_WIDTH: f32 = 200.0;
+pub const SIDEBAR_PADDING: f32 = 10.0;
+pub const SIDEBAR_BUTTON_HEIGHT: f32 = 45.0;
+pub const SIDEBAR_BUTTON_SPACING: f32 = 10.0;
+
+pub const TOAST_WIDTH: f32 = 320.0;
+pub const MAX_TOASTS: usize = 4;
+/// How long a toast is shown for, in `AnimationTick`s.
+pub const TOAST_DURATION: usize = 200;
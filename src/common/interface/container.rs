@@ -1,4 +1,6 @@
+use iced::Background;
 use iced::Border;
+use iced::Color;
 use iced::Theme;
 use iced::border::Radius;
 use iced::widget::button::Status as ButtonStatus;
@@ -6,6 +8,7 @@ use iced::widget::button::Style as ButtonStyle;
 use iced::widget::container;
 use iced::widget::container::Style as ContainerStyle;
 
+use crate::common::interface::color::DARK_GREY;
 use crate::common::interface::color::OFF_WHITE;
 use crate::common::interface::constants::BORDER_RADIUS;
 use crate::common::interface::constants::BORDER_WIDTH;
@@ -18,7 +21,7 @@ pub fn shadow_container() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
-pub(crate) fn content_container() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn content_container() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
@@ -29,7 +32,7 @@ pub(crate) fn content_container() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
-pub(crate) fn button_container() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
+pub fn button_container() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
     |_theme, status| {
         let text_color = match status {
             ButtonStatus::Hovered => OFF_WHITE.scale_alpha(0.7),
@@ -50,7 +53,7 @@ pub(crate) fn button_container() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle
     }
 }
 
-pub(crate) fn sidebar_container() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn sidebar_container() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
@@ -71,3 +74,16 @@ pub fn header_container() -> impl Fn(&iced::Theme) -> container::Style {
         ..Default::default()
     }
 }
+
+pub fn toast_container(color: Color) -> impl Fn(&Theme) -> ContainerStyle {
+    move |_theme| ContainerStyle {
+        background: Some(Background::Color(DARK_GREY)),
+        border: Border {
+            color,
+            width: BORDER_WIDTH,
+            radius: Radius::new(BORDER_RADIUS),
+        },
+        shadow: SHADOW_GRAY,
+        ..Default::default()
+    }
+}
@@ -1,6 +1,7 @@
-pub(crate) mod button;
-pub(crate) mod color;
-pub(crate) mod constants;
-pub(crate) mod container;
-pub(crate) mod font;
-pub(crate) mod shadow;
+pub mod button;
+pub mod color;
+pub mod constants;
+pub mod container;
+pub mod font;
+pub mod shadow;
+pub mod toast;
@@ -0,0 +1,83 @@
+use iced::Color;
+use iced::Element;
+use iced::Length::Fill;
+use iced::alignment::Horizontal;
+use iced::mouse::Interaction;
+use iced::widget::column;
+use iced::widget::container;
+use iced::widget::mouse_area;
+use iced::widget::text;
+use iced::widget::text::Wrapping;
+
+use crate::common::interface::constants::MAX_TOASTS;
+use crate::common::interface::constants::TOAST_DURATION;
+use crate::common::interface::constants::TOAST_WIDTH;
+use crate::common::interface::container::toast_container;
+use crate::common::interface::font::BERKELEY_MONO_BOLD;
+
+/// An in-app notification, shown on the top-right corner.
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub title: String,
+    pub body: String,
+    pub color: Color,
+    /// The `app_clock` value at which the toast is dismissed.
+    pub expires_at: usize,
+}
+
+impl Toast {
+    pub fn new(title: String, body: String, color: Color, app_clock: usize) -> Self {
+        Toast {
+            title,
+            body,
+            color,
+            expires_at: app_clock.wrapping_add(TOAST_DURATION),
+        }
+    }
+}
+
+/// Push a [`Toast`], dropping the oldest ones if there are too many.
+pub fn push_toast(toasts: &mut Vec<Toast>, toast: Toast) {
+    toasts.push(toast);
+    if toasts.len() > MAX_TOASTS {
+        toasts.drain(..toasts.len() - MAX_TOASTS);
+    }
+}
+
+/// Render the toasts, newest first. Clicking a toast emits `on_dismiss` with its index.
+pub fn view_toasts<'a, Message: Clone + 'a>(
+    toasts: &'a [Toast],
+    on_dismiss: impl Fn(usize) -> Message,
+) -> Element<'a, Message> {
+    let toasts = toasts
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(column![].spacing(8), |col, (idx, toast)| {
+            col.push(
+                mouse_area(
+                    container(
+                        column![
+                            text(&toast.title)
+                                .size(14)
+                                .font(BERKELEY_MONO_BOLD)
+                                .color(toast.color),
+                            text(&toast.body).size(12).wrapping(Wrapping::Glyph),
+                        ]
+                        .spacing(4),
+                    )
+                    .padding(10)
+                    .width(TOAST_WIDTH)
+                    .style(toast_container(toast.color)),
+                )
+                .on_press(on_dismiss(idx))
+                .interaction(Interaction::Pointer),
+            )
+        });
+
+    container(toasts)
+        .padding(20)
+        .width(Fill)
+        .align_x(Horizontal::Right)
+        .into()
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+
+/// The log file, in the active network's data directory.
+pub const LOG_FILE: &str = "bonsai.log";
+
+/// The default size a log file may grow to before it is rotated, in MiB.
+pub const LOG_MAX_SIZE: u64 = 50;
+
+/// The default number of rotated log files kept per network.
+pub const LOG_MAX_FILES: usize = 14;
+
+/// How much is kept of the logs written before the log directory is known.
+const MAX_PENDING: usize = 1024 * 1024;
+
+/// When log files are rotated, and how many are kept.
+#[derive(Clone, Copy, Debug)]
+pub struct LogRotation {
+    /// The size a log file may grow to before it is rotated, in bytes.
+    pub max_size: u64,
+    /// How many rotated log files are kept, besides the current one.
+    pub max_files: usize,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        LogRotation {
+            max_size: LOG_MAX_SIZE * 1024 * 1024,
+            max_files: LOG_MAX_FILES,
+        }
+    }
+}
+
+/// The log file of the active network, rotated daily and by size.
+///
+/// The current file is always [`LOG_FILE`]. Rotated files are renamed after the
+/// day they were written on, as `bonsai.log.2026-01-31`, followed by `.1`, `.2`...
+/// when a day is rotated more than once, and the oldest are removed beyond the
+/// [`LogRotation`]'s limit.
+///
+/// Logs written before [`LogFiles::open`] are held in memory, and written to the
+/// first directory opened, so nothing logged while loading the settings is lost.
+#[derive(Clone, Default)]
+pub struct LogFiles(Arc<Mutex<LogFileState>>);
+
+#[derive(Default)]
+struct LogFileState {
+    dir: Option<PathBuf>,
+    file: Option<File>,
+    /// The day the current file was started.
+    date: Option<NaiveDate>,
+    size: u64,
+    rotation: LogRotation,
+    pending: Vec<u8>,
+}
+
+impl LogFiles {
+    /// Write logs to `dir`, closing the previous directory's log file, if any.
+    pub fn open(&self, dir: &Path, rotation: LogRotation) {
+        let mut state = self.0.lock().unwrap();
+        state.rotation = rotation;
+        if state.dir.as_deref() == Some(dir) {
+            return;
+        }
+
+        state.file = None;
+        state.dir = Some(dir.to_path_buf());
+
+        let pending = std::mem::take(&mut state.pending);
+        if !pending.is_empty() {
+            if let Err(e) = state.write(&pending) {
+                // The logger can't log its own failures.
+                eprintln!("Failed to write to {}: {e}", dir.to_string_lossy());
+            }
+        }
+    }
+}
+
+impl Write for LogFiles {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.0.lock().unwrap().file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl LogFileState {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(dir) = self.dir.clone() else {
+            if self.pending.len() + buf.len() <= MAX_PENDING {
+                self.pending.extend_from_slice(buf);
+            }
+            return Ok(buf.len());
+        };
+
+        if self.file.is_none() {
+            self.open_file(&dir)?;
+        }
+
+        let today = Local::now().date_naive();
+        let new_day = self.date.is_some_and(|date| date != today);
+        let full = self.size + buf.len() as u64 > self.rotation.max_size;
+        if self.size > 0 && (new_day || full) {
+            self.rotate(&dir)?;
+        }
+
+        let file = self.file.as_mut().expect("opened above");
+        file.write_all(buf)?;
+        self.size += buf.len() as u64;
+
+        Ok(buf.len())
+    }
+
+    /// Open the log file in `dir`, appending to what a previous run left.
+    fn open_file(&mut self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(LOG_FILE))?;
+
+        let metadata = file.metadata()?;
+        self.size = metadata.len();
+        // A file left by a previous run belongs to the day it was last written.
+        self.date = Some(match metadata.modified() {
+            Ok(modified) if self.size > 0 => DateTime::<Local>::from(modified).date_naive(),
+            _ => Local::now().date_naive(),
+        });
+        self.file = Some(file);
+
+        Ok(())
+    }
+
+    /// Move the current file aside under its day, and start a new one.
+    fn rotate(&mut self, dir: &Path) -> io::Result<()> {
+        self.file = None;
+
+        let date = self.date.unwrap_or_else(|| Local::now().date_naive());
+        let base = format!("{LOG_FILE}.{}", date.format("%Y-%m-%d"));
+        let mut rotated = dir.join(&base);
+        let mut index = 1;
+        while rotated.exists() {
+            rotated = dir.join(format!("{base}.{index}"));
+            index += 1;
+        }
+        fs::rename(dir.join(LOG_FILE), &rotated)?;
+
+        if let Err(e) = prune(dir, self.rotation.max_files) {
+            eprintln!(
+                "Failed to remove old logs in {}: {e}",
+                dir.to_string_lossy()
+            );
+        }
+
+        self.open_file(dir)
+    }
+}
+
+/// Remove the oldest rotated log files in `dir`, keeping `max_files`.
+fn prune(dir: &Path, max_files: usize) -> io::Result<()> {
+    let prefix = format!("{LOG_FILE}.");
+    let mut rotated: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect();
+
+    // Newest first.
+    rotated.sort_by(|a, b| b.0.cmp(&a.0));
+    for (_, path) in rotated.into_iter().skip(max_files) {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
@@ -1,31 +1,191 @@
+use core::fmt::Display;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::error;
+use tracing::info;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Registry;
 use tracing_subscriber::fmt;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
 use tracing_subscriber::util::SubscriberInitExt;
 
-use crate::node::control::DATA_DIR;
-use crate::node::control::NETWORK;
+use crate::common::log_file::LogFiles;
+use crate::node::log_capture::LOG_CAPACITY;
 use crate::node::log_capture::LogCapture;
 use crate::node::log_capture::LogCaptureLayer;
 
-pub(crate) fn setup_logger() -> LogCapture {
-    // Create the data directory, if needed.
-    let data_dir = format!("{}{}", DATA_DIR, NETWORK);
-    std::fs::create_dir_all(&data_dir).expect("Failed to create data directory");
+/// Directives for targets without a level in [`LogLevels`].
+const BASE_LOG_FILTER: &str = "info,iced=error";
+
+/// Swaps the filter of the running subscriber.
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Whether `--log-level` or `RUST_LOG` set the filter at startup.
+static FILTER_OVERRIDDEN: OnceLock<bool> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// The next level, cycling from the quietest to the most verbose and then off.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Error,
+            Self::Error => Self::Warn,
+            Self::Warn => Self::Info,
+            Self::Info => Self::Debug,
+            Self::Debug => Self::Trace,
+            Self::Trace => Self::Off,
+        }
+    }
+
+    fn directive(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.directive().to_uppercase())
+    }
+}
+
+/// The targets whose level can be set from the Settings tab.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevelTarget {
+    Bonsai,
+    BdkFloresta,
+    FlorestaChain,
+    FlorestaWire,
+}
+
+impl LogLevelTarget {
+    pub const TARGETS: [LogLevelTarget; 4] = [
+        LogLevelTarget::Bonsai,
+        LogLevelTarget::BdkFloresta,
+        LogLevelTarget::FlorestaChain,
+        LogLevelTarget::FlorestaWire,
+    ];
+
+    /// The target prefix, which also matches the `bonsaid` daemon for [`LogLevelTarget::Bonsai`].
+    fn target(self) -> &'static str {
+        match self {
+            Self::Bonsai => "bonsai",
+            Self::BdkFloresta => "bdk_floresta",
+            Self::FlorestaChain => "floresta_chain",
+            Self::FlorestaWire => "floresta_wire",
+        }
+    }
+}
 
-    let file_appender = tracing_appender::rolling::never(&data_dir, "bonsai.log");
-    let (non_blocking_file, _guard) = tracing_appender::non_blocking(file_appender);
+impl Display for LogLevelTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bonsai => write!(f, "BONSAI"),
+            Self::BdkFloresta => write!(f, "BDK"),
+            Self::FlorestaChain => write!(f, "CHAIN"),
+            Self::FlorestaWire => write!(f, "WIRE"),
+        }
+    }
+}
+
+/// The log level of each [`LogLevelTarget`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogLevels {
+    pub bonsai: LogLevel,
+    pub bdk_floresta: LogLevel,
+    pub floresta_chain: LogLevel,
+    pub floresta_wire: LogLevel,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        LogLevels {
+            bonsai: LogLevel::Debug,
+            bdk_floresta: LogLevel::Info,
+            floresta_chain: LogLevel::Info,
+            floresta_wire: LogLevel::Info,
+        }
+    }
+}
+
+impl LogLevels {
+    pub fn level(&self, target: LogLevelTarget) -> LogLevel {
+        match target {
+            LogLevelTarget::Bonsai => self.bonsai,
+            LogLevelTarget::BdkFloresta => self.bdk_floresta,
+            LogLevelTarget::FlorestaChain => self.floresta_chain,
+            LogLevelTarget::FlorestaWire => self.floresta_wire,
+        }
+    }
+
+    pub fn set_level(&mut self, target: LogLevelTarget, level: LogLevel) {
+        match target {
+            LogLevelTarget::Bonsai => self.bonsai = level,
+            LogLevelTarget::BdkFloresta => self.bdk_floresta = level,
+            LogLevelTarget::FlorestaChain => self.floresta_chain = level,
+            LogLevelTarget::FlorestaWire => self.floresta_wire = level,
+        }
+    }
+
+    /// The `EnvFilter` directives for these levels.
+    fn directives(&self) -> String {
+        LogLevelTarget::TARGETS
+            .iter()
+            .fold(BASE_LOG_FILTER.to_string(), |directives, target| {
+                format!(
+                    "{directives},{}={}",
+                    target.target(),
+                    self.level(*target).directive()
+                )
+            })
+    }
+}
+
+/// Setup logging to stdout, [`LogFiles`], and the [`LogCapture`].
+///
+/// Nothing is written to disk until [`LogFiles::open`] is given the active
+/// network's data directory, which is only known once the settings are loaded.
+/// The filter starts from `log_level`, which takes precedence over `RUST_LOG`,
+/// and can be replaced later with [`set_log_levels`].
+pub fn setup_logger(log_level: Option<&str>) -> (LogCapture, LogFiles) {
+    let log_files = LogFiles::default();
+    let (non_blocking_file, _guard) = tracing_appender::non_blocking(log_files.clone());
     std::mem::forget(_guard);
 
-    let log_capture = LogCapture::new(1_000_000);
+    let log_capture = LogCapture::new(LOG_CAPACITY);
     let capture_layer = LogCaptureLayer::new(log_capture.clone());
 
+    let filter = match log_level {
+        Some(log_level) => Some(EnvFilter::new(log_level)),
+        None => EnvFilter::try_from_default_env().ok(),
+    };
+    let _ = FILTER_OVERRIDDEN.set(filter.is_some());
+    let filter = filter.unwrap_or_else(|| EnvFilter::new(LogLevels::default().directives()));
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER_HANDLE.set(handle);
+
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-            EnvFilter::new(
-                "info,iced=error,bonsai=debug,bdk_floresta=info,floresta_chain=info,floresta_wire=info",
-            )
-        }))
+        .with(filter)
         .with(
             fmt::layer()
                 .with_writer(std::io::stdout)
@@ -43,5 +203,25 @@ pub(crate) fn setup_logger() -> LogCapture {
 
     tracing::info!("Tracing subscriber setup");
 
-    log_capture
+    (log_capture, log_files)
+}
+
+/// Apply the saved log levels at startup, unless `--log-level` or `RUST_LOG` set the filter.
+pub fn init_log_levels(levels: &LogLevels) {
+    if !FILTER_OVERRIDDEN.get().copied().unwrap_or(false) {
+        set_log_levels(levels);
+    }
+}
+
+/// Replace the log filter with `levels`.
+pub fn set_log_levels(levels: &LogLevels) {
+    let Some(handle) = FILTER_HANDLE.get() else {
+        return;
+    };
+
+    let directives = levels.directives();
+    match handle.reload(EnvFilter::new(&directives)) {
+        Ok(()) => info!("Log filter set to {directives}"),
+        Err(e) => error!("Failed to set log filter: {e}"),
+    }
 }
@@ -1,3 +1,6 @@
-pub(crate) mod interface;
-pub(crate) mod logger;
-pub(crate) mod util;
+pub mod diagnostics;
+pub mod interface;
+pub mod log_file;
+pub mod logger;
+pub mod tab;
+pub mod util;
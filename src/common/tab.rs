@@ -0,0 +1,11 @@
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Tab {
+    Wallet,
+    #[default]
+    NodeMetrics,
+    NodeNetwork,
+    NodeBlocks,
+    NodeUtreexo,
+    Settings,
+    About,
+}
@@ -2,7 +2,7 @@ use core::fmt::Display;
 use std::time::Duration;
 
 /// Format a [`Duration`] to HH:MM:SS.
-pub(crate) fn format_duration(duration: Duration) -> String {
+pub fn format_duration(duration: Duration) -> String {
     let total_secs = duration.as_secs();
     let hours = total_secs / 3600;
     let minutes = (total_secs % 3600) / 60;
@@ -10,7 +10,12 @@ pub(crate) fn format_duration(duration: Duration) -> String {
     format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
 }
 
-pub(crate) fn format_thousands<T: Display>(n: T) -> String {
+/// Format a size in bytes to MiB.
+pub fn format_mebibytes(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+pub fn format_thousands<T: Display>(n: T) -> String {
     let s = n.to_string();
     let negative = s.starts_with('-');
 
@@ -0,0 +1,5 @@
+//! The node, settings and shared code of the `bonsai` GUI and the `bonsaid` daemon.
+
+pub mod common;
+pub mod node;
+pub mod settings;
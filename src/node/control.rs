@@ -1,7 +1,16 @@
 use core::fmt::Display;
+use core::hash::Hash;
+use core::hash::Hasher;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -11,49 +20,262 @@ use bdk_floresta::UtreexoNodeConfig;
 use bdk_floresta::UtxoData;
 use bdk_floresta::builder::Builder;
 use bitcoin::Block;
+use bitcoin::BlockHash;
 use bitcoin::Network;
 use bitcoin::OutPoint;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use iced::Color;
 use iced::Element;
 use iced::Subscription;
 use iced::Task;
 use iced::clipboard;
 use iced::futures::SinkExt;
+use iced::futures::Stream;
 use iced::widget::qr_code;
-use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
 use tokio::runtime::Handle;
+use tokio::runtime::RuntimeFlavor;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::SendError;
+use tokio::sync::mpsc::error::TrySendError;
 use tracing::error;
 use tracing::info;
+use tracing::warn;
 
-use crate::Tab;
+use crate::common::interface::color::GREEN_SHAMROCK;
+use crate::common::interface::color::OFF_WHITE;
+use crate::common::interface::color::RED;
+use crate::common::interface::color::pulse_color;
+use crate::common::tab::Tab;
 use crate::common::util::format_thousands;
 use crate::node::error::BonsaiNodeError;
+use crate::node::events;
+use crate::node::events::EVENT_SOCKET_FILE;
+use crate::node::events::Event;
+use crate::node::events::EventPublisher;
+use crate::node::exporter;
+use crate::node::fee_estimator::BlockFeerates;
+use crate::node::fee_estimator::FEE_ESTIMATOR_WINDOW;
+use crate::node::fee_estimator::FeeEstimator;
 use crate::node::geoip::GeoIpReader;
+use crate::node::lock::DataDirLock;
 use crate::node::log_capture::LogCapture;
+use crate::node::log_capture::LogView;
 use crate::node::message::NodeMessage;
+use crate::node::proxy::PROXY_CHECK_INTERVAL;
+use crate::node::proxy::ProxyStatus;
+use crate::node::proxy::check_proxy;
+use crate::node::rest;
+use crate::node::rpc;
+use crate::node::shutdown::ShutdownProgress;
+use crate::node::shutdown::TaskCanceller;
+use crate::node::shutdown::WeakNode;
+use crate::node::shutdown::shutdown;
 use crate::node::stats_fetcher::NodeStatistics;
 use crate::node::stats_fetcher::fetch_stats;
+use crate::node::sync_history::SyncHistory;
 
-pub const DATA_DIR: &str = "./data/";
-pub const NETWORK: Network = Network::Signet;
 pub const FETCH_STATISTICS_TIME: u64 = 1;
-
-static BLOCK_RECEIVER: Lazy<Arc<Mutex<Option<mpsc::UnboundedReceiver<Block>>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(None)));
+/// Delay before the first automatic restart of a failed node, in seconds.
+pub const RETRY_BASE_DELAY: u64 = 2;
+/// Upper bound for the delay between automatic restarts, in seconds.
+pub const RETRY_MAX_DELAY: u64 = 300;
+/// How many times a failed node is restarted automatically.
+pub const MAX_RETRY_ATTEMPTS: u32 = 8;
+/// How long a node behind its peers may go without validating a block, in minutes.
+pub const STALL_TIMEOUT: u64 = 20;
+/// How many restarts are kept for the Metrics tab.
+const MAX_RESTART_RECORDS: usize = 20;
+
+/// How many validated blocks can be waiting for the UI before the node is slowed down.
+pub const BLOCK_CHANNEL_CAPACITY: usize = 16;
+/// How old a block may be for the node to be considered out of IBD, in seconds.
+const CAUGHT_UP_BLOCK_AGE: i64 = 24 * 60 * 60;
+
+/// Identifies the node instances started by [`start_node`].
+static NEXT_NODE_ID: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Clone, Debug, Default)]
-pub(crate) enum NodeStatus {
+pub enum NodeStatus {
     #[default]
     Inactive,
     Starting,
     Running,
     ShuttingDown,
-    #[allow(unused)]
     Failed(BonsaiNodeError),
 }
 
+impl NodeStatus {
+    /// The color a [`NodeStatus`] is displayed with.
+    pub fn color(&self, app_clock: usize) -> Color {
+        match self {
+            Self::Starting => pulse_color(GREEN_SHAMROCK, app_clock),
+            Self::Running => GREEN_SHAMROCK,
+            Self::Inactive => OFF_WHITE,
+            Self::ShuttingDown => pulse_color(RED, app_clock),
+            Self::Failed(_) => RED,
+        }
+    }
+}
+
+/// When the [`Supervisor`] restarts the node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    Never,
+    /// After failures that may be transient, including stalls.
+    #[default]
+    OnFailure,
+    /// After every failure it is safe to restart from, such as a locked data directory.
+    Always,
+}
+
+impl Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Never => write!(f, "NEVER"),
+            Self::OnFailure => write!(f, "ON FAILURE"),
+            Self::Always => write!(f, "ALWAYS"),
+        }
+    }
+}
+
+/// An automatic restart of the node, shown in the Metrics tab.
+#[derive(Clone, Debug)]
+pub struct RestartRecord {
+    pub time: DateTime<Local>,
+    pub attempt: u32,
+    pub reason: String,
+}
+
+/// Watches the node's health through its statistics, and restarts
+/// it after failures according to a [`RestartPolicy`].
+pub struct Supervisor {
+    pub policy: RestartPolicy,
+    pub max_attempts: u32,
+    pub stall_timeout: Duration,
+    /// Consecutive restarts since the node last made progress.
+    pub attempts: u32,
+    /// When the next automatic restart happens.
+    pub retry_at: Option<Instant>,
+    /// The last validated height, and when it was first seen.
+    progress: Option<(u32, Instant)>,
+    /// Automatic restarts of this session, newest first.
+    pub restarts: VecDeque<RestartRecord>,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Supervisor {
+            policy: RestartPolicy::default(),
+            max_attempts: MAX_RETRY_ATTEMPTS,
+            stall_timeout: Duration::from_secs(STALL_TIMEOUT * 60),
+            attempts: 0,
+            retry_at: None,
+            progress: None,
+            restarts: VecDeque::new(),
+        }
+    }
+}
+
+impl Supervisor {
+    pub fn new(policy: RestartPolicy, max_attempts: u32, stall_timeout: Duration) -> Self {
+        Supervisor {
+            policy,
+            max_attempts,
+            stall_timeout,
+            ..Supervisor::default()
+        }
+    }
+
+    pub fn configure(&mut self, policy: RestartPolicy, max_attempts: u32, stall_timeout: Duration) {
+        self.policy = policy;
+        self.max_attempts = max_attempts;
+        self.stall_timeout = stall_timeout;
+    }
+
+    /// Give a node started by hand a fresh set of attempts, unless a restart is pending.
+    fn start_requested(&mut self) {
+        if self.retry_at.is_none() {
+            self.attempts = 0;
+        }
+    }
+
+    /// Forget the progress of the previous node instance.
+    fn node_started(&mut self) {
+        self.retry_at = None;
+        self.progress = None;
+    }
+
+    /// Check the node's health, returning why it is unhealthy.
+    ///
+    /// A node is stalled if it validated no block for the stall timeout while
+    /// behind its peers. A node at the tip is never stalled, since blocks can
+    /// be an hour apart.
+    fn check_health(&mut self, stats: &NodeStatistics) -> Option<BonsaiNodeError> {
+        let now = Instant::now();
+
+        match self.progress {
+            Some((height, since)) if stats.blocks <= height => {
+                let behind = stats.blocks < stats.headers;
+                let stalled_for = now.duration_since(since);
+                if behind
+                    && !stats.peer_informations.is_empty()
+                    && stalled_for >= self.stall_timeout
+                {
+                    return Some(BonsaiNodeError::Stalled(stalled_for.as_secs() / 60));
+                }
+            }
+            Some(_) => {
+                // Progress means the node recovered.
+                self.attempts = 0;
+                self.progress = Some((stats.blocks, now));
+            }
+            None => self.progress = Some((stats.blocks, now)),
+        }
+
+        None
+    }
+
+    /// Schedule a restart after a failure, if the policy allows it,
+    /// returning the attempt number and the delay before it.
+    fn schedule_restart(&mut self, error: &BonsaiNodeError) -> Option<(u32, Duration)> {
+        self.attempts += 1;
+
+        let allowed = match self.policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => error.is_retryable(),
+            RestartPolicy::Always => error.is_restartable(),
+        };
+        if !allowed || self.attempts > self.max_attempts {
+            self.retry_at = None;
+            return None;
+        }
+
+        let delay = retry_delay(self.attempts);
+        info!(
+            "Restarting the node in {}s [attempt {}/{}]: {error}",
+            delay.as_secs(),
+            self.attempts,
+            self.max_attempts
+        );
+        self.retry_at = Some(Instant::now() + delay);
+        self.restarts.push_front(RestartRecord {
+            time: Local::now(),
+            attempt: self.attempts,
+            reason: error.to_string(),
+        });
+        self.restarts.truncate(MAX_RESTART_RECORDS);
+
+        Some((self.attempts, delay))
+    }
+}
+
 impl Display for NodeStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
@@ -61,49 +283,166 @@ impl Display for NodeStatus {
             Self::Starting => write!(f, "STARTING"),
             Self::Running => write!(f, "RUNNING"),
             Self::ShuttingDown => write!(f, "SHUTTING DOWN"),
-            Self::Failed(e) => write!(f, "FAILED [{}]", e),
+            Self::Failed(_) => write!(f, "FAILED"),
+        }
+    }
+}
+
+pub struct BlockForwarder {
+    tx: mpsc::Sender<Block>,
+    fee_estimator: FeeEstimator,
+    events: EventPublisher,
+    /// Height and hash of the last block seen, to detect reorgs.
+    tip: StdMutex<Option<(u32, BlockHash)>>,
+    /// Set once a recent block is seen, and never cleared, like Bitcoin Core's IBD latch.
+    caught_up: AtomicBool,
+    /// Held by the forwarder since the node owns it, so the data
+    /// directory stays locked for exactly as long as the node lives.
+    _datadir_lock: DataDirLock,
+}
+
+impl BlockForwarder {
+    /// Send a block to the UI, waiting for room in the channel if it is full.
+    fn forward(&self, block: Block) {
+        // `on_block` is synchronous, so waiting must not stall the runtime's other tasks.
+        let result = match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.tx.blocking_send(block))
+            }
+            Ok(_) => self.tx.try_send(block).map_err(|e| match e {
+                TrySendError::Full(block) | TrySendError::Closed(block) => SendError(block),
+            }),
+            Err(_) => self.tx.blocking_send(block),
+        };
+
+        if let Err(SendError(block)) = result {
+            error!(
+                "Failed to forward block {} to the UI",
+                block.header.block_hash()
+            );
+        }
+    }
+}
+
+impl BlockForwarder {
+    /// Publish a block, and a reorg if it doesn't build on the previous tip.
+    fn publish(&self, block: &Block, height: u32) {
+        let hash = block.block_hash();
+        let previous_tip = self.tip.lock().unwrap().replace((height, hash));
+
+        if !self.events.has_subscribers() {
+            return;
+        }
+
+        if let Some((old_height, old_tip)) = previous_tip
+            && height <= old_height
+            && block.header.prev_blockhash != old_tip
+        {
+            self.events
+                .publish(Event::reorg(height, old_tip, old_height, hash));
         }
+        self.events.publish(Event::block(block, height));
+        self.events.publish(Event::raw_block(block, height));
     }
 }
 
-pub(crate) struct BlockForwarder {
-    tx: mpsc::UnboundedSender<Block>,
+/// The receiving end of a node's block channel.
+///
+/// Hashed by the id of the node it belongs to, so the block
+/// subscription is recreated whenever a new node starts.
+#[derive(Clone)]
+pub struct BlockReceiver {
+    node_id: u64,
+    receiver: Arc<Mutex<mpsc::Receiver<Block>>>,
+}
+
+impl BlockReceiver {
+    /// Wait for the next block, or `None` once the node has dropped the sending end.
+    pub async fn recv(&self) -> Option<Block> {
+        self.receiver.lock().await.recv().await
+    }
+}
+
+impl Hash for BlockReceiver {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_id.hash(state);
+    }
 }
 
 impl BlockConsumer for BlockForwarder {
     fn on_block(
         &self,
         block: &Block,
-        _height: u32,
-        _spent_utxos: Option<&HashMap<OutPoint, UtxoData>>,
+        height: u32,
+        spent_utxos: Option<&HashMap<OutPoint, UtxoData>>,
     ) {
-        let _ = self.tx.send(block.clone());
+        if !self.caught_up.load(Ordering::Relaxed)
+            && Utc::now().timestamp() - i64::from(block.header.time) < CAUGHT_UP_BLOCK_AGE
+        {
+            self.caught_up.store(true, Ordering::Relaxed);
+        }
+
+        if let Some(spent_utxos) = spent_utxos
+            && let Some(feerates) = BlockFeerates::from_block(block, height, spent_utxos)
+        {
+            self.fee_estimator.record(feerates);
+        }
+
+        self.publish(block, height);
+        self.forward(block.clone());
     }
 
-    #[allow(unused)]
+    /// Spent UTXOs are only wanted once the node is out of IBD.
+    ///
+    /// They make the node collect the previous output of every input of every block,
+    /// while the fee estimator only keeps the last [`FEE_ESTIMATOR_WINDOW`] blocks,
+    /// which IBD blocks are pushed out of long before the node catches up.
     fn wants_spent_utxos(&self) -> bool {
-        false
+        self.caught_up.load(Ordering::Relaxed)
     }
 }
 
 #[derive(Default)]
-pub(crate) struct EmbeddedNode {
-    pub(crate) config: Option<UtreexoNodeConfig>,
-    pub(crate) handle: Option<Arc<RwLock<Node>>>,
-    pub(crate) status: NodeStatus,
-    pub(crate) statistics: Option<NodeStatistics>,
-    pub(crate) subscription_active: bool,
-    pub(crate) is_shutting_down: bool,
-    pub(crate) log_capture: LogCapture,
-    pub(crate) last_log_version: usize,
-    pub(crate) start_time: Option<Instant>,
-    pub(crate) peer_input: String,
-    pub(crate) geoip_reader: Option<GeoIpReader>,
-    pub(crate) accumulator_qr_data: Option<qr_code::Data>,
-    pub(crate) block_explorer_height_str: String,
-    pub(crate) latest_blocks: Vec<Block>,
-    pub(crate) block_explorer_current_block: Option<Block>,
-    pub(crate) block_explorer_expanded_tx_idx: Option<usize>,
+pub struct EmbeddedNode {
+    pub config: Option<UtreexoNodeConfig>,
+    pub handle: Option<Arc<RwLock<Node>>>,
+    pub block_receiver: Option<BlockReceiver>,
+    pub status: NodeStatus,
+    pub statistics: Option<NodeStatistics>,
+    pub subscription_active: bool,
+    pub is_shutting_down: bool,
+    pub log_capture: LogCapture,
+    pub log_view: LogView,
+    pub last_log_version: usize,
+    pub start_time: Option<Instant>,
+    pub peer_input: String,
+    pub geoip_reader: Option<GeoIpReader>,
+    pub accumulator_qr_data: Option<qr_code::Data>,
+    pub block_explorer_height_str: String,
+    pub latest_blocks: Vec<Block>,
+    pub block_explorer_current_block: Option<Block>,
+    pub block_explorer_expanded_tx_idx: Option<usize>,
+    pub fee_estimator: FeeEstimator,
+    /// The validated height over time, saved in the node's data directory.
+    pub sync_history: SyncHistory,
+    /// Whether the running node's SOCKS5 proxy accepts connections.
+    pub proxy_status: ProxyStatus,
+    /// The port to serve JSON-RPC on while the node runs, if enabled.
+    pub rpc_port: Option<u16>,
+    /// The address to serve REST on while the node runs, if enabled.
+    pub rest_address: Option<SocketAddr>,
+    /// The port to export metrics on while the node runs, if enabled.
+    pub metrics_port: Option<u16>,
+    /// Whether to publish events on a Unix socket while the node runs.
+    pub event_socket: bool,
+    pub events: EventPublisher,
+    /// Restarts the node when it fails or stalls.
+    pub supervisor: Supervisor,
+    /// Cancels in-flight tasks holding [`EmbeddedNode::handle`] on shutdown.
+    pub canceller: TaskCanceller,
+    pub shutdown_progress: Option<ShutdownProgress>,
+    /// Whether to start the node again once the current shutdown finishes.
+    pub restart_after_shutdown: bool,
 }
 
 impl EmbeddedNode {
@@ -114,111 +453,130 @@ impl EmbeddedNode {
                 let current_version = self.log_capture.version();
                 if current_version != self.last_log_version {
                     self.last_log_version = current_version;
+                    self.log_view.sync(&self.log_capture);
                 }
 
                 Task::none()
             }
             NodeMessage::Start => {
                 self.status = NodeStatus::Starting;
+                self.supervisor.start_requested();
 
                 if let Some(config) = self.config.clone() {
-                    Task::perform(start_node(config), |res| match res {
-                        Ok(handle) => NodeMessage::Running(handle),
-                        Err(e) => NodeMessage::Error(BonsaiNodeError::from(e)),
-                    })
+                    self.sync_history.load(Path::new(&config.datadir));
+                    Task::perform(
+                        start_node(config, self.fee_estimator.clone(), self.events.clone()),
+                        |res| match res {
+                            Ok((handle, block_receiver)) => {
+                                NodeMessage::Running(handle, block_receiver)
+                            }
+                            Err(e) => NodeMessage::Failed(e),
+                        },
+                    )
                 } else {
-                    Task::done(NodeMessage::Error(BonsaiNodeError::Generic(
+                    Task::done(NodeMessage::Failed(BonsaiNodeError::Generic(
                         "No node configuration available".to_string(),
                     )))
                 }
             }
-            NodeMessage::Restart => {
-                self.status = NodeStatus::ShuttingDown;
-                self.subscription_active = false;
-                self.is_shutting_down = true;
-                self.start_time = None;
-
-                if let Some(node_handle) = self.handle.take() {
-                    let rt_handle = Handle::current();
-
-                    Task::future(async move {
-                        let result = rt_handle
-                            .spawn(async move { stop_node(node_handle).await })
-                            .await;
-
-                        match result {
-                            Ok(Ok(_)) => NodeMessage::Start,
-                            Ok(Err(e)) => NodeMessage::Error(BonsaiNodeError::from(e)),
-                            Err(e) => NodeMessage::Error(BonsaiNodeError::Generic(e.to_string())),
-                        }
-                    })
-                } else {
-                    Task::done(NodeMessage::Start)
-                }
-            }
+            NodeMessage::Restart => self.begin_shutdown(true),
             NodeMessage::Starting => {
                 self.status = NodeStatus::Starting;
                 self.subscription_active = false;
                 Task::none()
             }
-            NodeMessage::Running(handle) => {
+            NodeMessage::Running(handle, block_receiver) => {
                 self.handle = Some(handle.clone());
+                self.block_receiver = Some(block_receiver);
                 self.status = NodeStatus::Running;
                 self.subscription_active = true;
                 self.is_shutting_down = false;
                 self.start_time = Some(Instant::now());
+                self.supervisor.node_started();
 
                 // Get the actual config from the running node and emit it
                 let handle_clone = handle.clone();
-                Task::future(async move {
+                let token = self.canceller.token();
+                let config_task = Task::future(token.run(async move {
                     let node = handle_clone.read().await;
                     match node.get_config().await {
                         Ok(config) => NodeMessage::ConfigUsed(config),
+                        // The node no longer answers, so it is considered failed.
                         Err(e) => {
                             error!("Failed to get node config: {}", e);
-                            NodeMessage::Error(BonsaiNodeError::from(e))
+                            NodeMessage::Failed(BonsaiNodeError::from(e))
                         }
                     }
-                })
+                }))
+                .and_then(Task::done);
+
+                // The servers live as long as this node instance.
+                let rpc_task = match (self.rpc_port, &self.config) {
+                    (Some(port), Some(config)) => self.spawn_server(rpc::serve(
+                        WeakNode::new(&handle, self.canceller.token()),
+                        config.network,
+                        port,
+                        PathBuf::from(&config.datadir),
+                    )),
+                    _ => Task::none(),
+                };
+
+                let rest_task = match (self.rest_address, &self.config) {
+                    (Some(address), Some(config)) => self.spawn_server(rest::serve(
+                        WeakNode::new(&handle, self.canceller.token()),
+                        config.network,
+                        address,
+                    )),
+                    _ => Task::none(),
+                };
+
+                let metrics_task = match (self.metrics_port, &self.config) {
+                    (Some(port), Some(config)) => self.spawn_server(exporter::serve(
+                        WeakNode::new(&handle, self.canceller.token()),
+                        config.network,
+                        port,
+                        self.start_time.unwrap_or_else(Instant::now),
+                    )),
+                    _ => Task::none(),
+                };
+
+                let events_task = match &self.config {
+                    Some(config) if self.event_socket => self.spawn_server(events::serve(
+                        PathBuf::from(&config.datadir).join(EVENT_SOCKET_FILE),
+                        self.events.clone(),
+                        self.canceller.token(),
+                    )),
+                    _ => Task::none(),
+                };
+
+                Task::batch([
+                    config_task,
+                    rpc_task,
+                    rest_task,
+                    metrics_task,
+                    events_task,
+                    Task::done(NodeMessage::CheckProxy),
+                ])
             }
             NodeMessage::ConfigUsed(_config) => {
                 // This will be handled in Bonsai to update settings
                 Task::none()
             }
-            NodeMessage::Shutdown => {
-                self.status = NodeStatus::ShuttingDown;
-                //self.subscription_active = false;
-                self.is_shutting_down = true;
-                self.start_time = None;
-
-                if let Some(node_handle) = self.handle.take() {
-                    let rt_handle = Handle::current();
-
-                    Task::future(async move {
-                        let result = rt_handle
-                            .spawn(async move { stop_node(node_handle).await })
-                            .await;
-
-                        match result {
-                            Ok(Ok(_)) => NodeMessage::ShutdownComplete,
-                            Ok(Err(e)) => NodeMessage::Error(BonsaiNodeError::from(e)),
-                            Err(e) => NodeMessage::Error(BonsaiNodeError::Generic(e.to_string())),
-                        }
-                    })
-                } else {
-                    Task::done(NodeMessage::ShutdownComplete)
-                }
+            NodeMessage::Shutdown => self.begin_shutdown(false),
+            NodeMessage::ShutdownProgress(progress) => {
+                info!("Node shutdown: {progress}");
+                self.shutdown_progress = Some(progress);
+                Task::none()
             }
-            NodeMessage::ShuttingDown => {
-                self.status = NodeStatus::ShuttingDown;
-                //self.subscription_active = false;
-                self.is_shutting_down = true;
-
-                if let Some(stats) = &mut self.statistics {
-                    stats.peer_informations.clear();
+            NodeMessage::ShutdownFinished(result) => {
+                self.shutdown_progress = None;
+                let restart = std::mem::take(&mut self.restart_after_shutdown);
+
+                match result {
+                    Ok(_) if restart => Task::done(NodeMessage::Start),
+                    Ok(_) => Task::done(NodeMessage::ShutdownComplete),
+                    Err(e) => Task::done(NodeMessage::Failed(e)),
                 }
-
-                Task::none()
             }
             NodeMessage::ShutdownComplete => {
                 self.status = NodeStatus::Inactive;
@@ -239,34 +597,119 @@ impl EmbeddedNode {
                         .as_ref()
                         .and_then(|encoded| qr_code::Data::new(encoded).ok());
 
+                    self.publish_peer_events(&stats);
+                    self.sync_history.record(stats.blocks, stats.headers);
+                    let unhealthy = self.supervisor.check_health(&stats);
                     self.statistics = Some(stats);
+
+                    if let Some(e) = unhealthy {
+                        return Task::done(NodeMessage::Failed(e));
+                    }
+                }
+                Task::none()
+            }
+            NodeMessage::CheckProxy => match self.config.as_ref().and_then(|c| c.proxy) {
+                Some(proxy) => {
+                    if !matches!(
+                        self.proxy_status,
+                        ProxyStatus::Reachable(p) | ProxyStatus::Unreachable(p, _) if p == proxy
+                    ) {
+                        self.proxy_status = ProxyStatus::Checking(proxy);
+                    }
+                    Task::perform(check_proxy(proxy), NodeMessage::ProxyChecked)
+                }
+                None => {
+                    self.proxy_status = ProxyStatus::Disabled;
+                    Task::none()
+                }
+            },
+            NodeMessage::ProxyChecked(status) => {
+                // Only report changes, as the proxy is checked periodically.
+                match &status {
+                    // The proxy's address is left out, as logs end up in diagnostics bundles.
+                    ProxyStatus::Unreachable(_, e)
+                        if !matches!(self.proxy_status, ProxyStatus::Unreachable(..)) =>
+                    {
+                        warn!("SOCKS5 proxy is unreachable: {e}");
+                    }
+                    ProxyStatus::Reachable(_)
+                        if !matches!(self.proxy_status, ProxyStatus::Reachable(_)) =>
+                    {
+                        info!("SOCKS5 proxy is reachable");
+                    }
+                    _ => {}
                 }
+                self.proxy_status = status;
                 Task::none()
             }
+            // Errors that stop the node get the same handling, and retries, as a failed start.
+            NodeMessage::Error(e) if e.is_fatal() && matches!(self.status, NodeStatus::Running) => {
+                self.update(NodeMessage::Failed(e))
+            }
+            // Errors of a single operation, such as connecting to a peer, are not fatal.
             NodeMessage::Error(e) => {
-                //TODO fix this
-                //self.status = NodeStatus::Failed(err);
-                //self.subscription_active = false;
                 error!("Node Error: {e}");
                 Task::none()
             }
+            NodeMessage::Failed(e) => {
+                error!("Node Failed: {e}");
+                let restart = self.supervisor.schedule_restart(&e);
+                self.status = NodeStatus::Failed(e);
+                self.subscription_active = false;
+                self.is_shutting_down = false;
+                self.start_time = None;
+                self.sync_history.stopped();
+
+                // Release a node that failed while running, before
+                // restarting on the same data directory.
+                self.block_receiver = None;
+                let release = match self.handle.take() {
+                    Some(node_handle) => {
+                        let canceller = std::mem::take(&mut self.canceller);
+                        Task::run(shutdown(node_handle, canceller), |message| message).discard()
+                    }
+                    None => Task::none(),
+                };
+
+                match restart {
+                    Some((attempt, delay)) => release
+                        .chain(Task::perform(tokio::time::sleep(delay), move |_| {
+                            NodeMessage::Retry(attempt)
+                        })),
+                    None => release,
+                }
+            }
+            NodeMessage::Retry(attempt) => {
+                // Ignore restarts scheduled before a later failure, or a manual start.
+                if matches!(self.status, NodeStatus::Failed(_))
+                    && attempt == self.supervisor.attempts
+                {
+                    let task = self.update(NodeMessage::Start);
+                    self.supervisor.retry_at = None;
+                    task
+                } else {
+                    Task::none()
+                }
+            }
             NodeMessage::GetStatistics => {
                 if self.subscription_active {
                     if let Some(handle) = &self.handle {
                         let handle = handle.clone();
                         let rt_handle = Handle::current();
                         let start_time = self.start_time;
+                        let token = self.canceller.token();
 
                         Task::future(async move {
                             rt_handle
-                                .spawn(async move { fetch_stats(handle, start_time).await })
+                                .spawn(token.run(fetch_stats(handle, start_time)))
                                 .await
                                 .unwrap_or_else(|_| {
-                                    NodeMessage::Error(BonsaiNodeError::Generic(
+                                    Some(NodeMessage::Failed(BonsaiNodeError::Generic(
                                         "Failed to fetch stats".to_string(),
-                                    ))
+                                    )))
                                 })
                         })
+                        .and_then(Task::done)
                     } else {
                         Task::none()
                     }
@@ -284,10 +727,11 @@ impl EmbeddedNode {
                 if let Some(handle) = &self.handle {
                     let handle = handle.clone();
                     let rt_handle = Handle::current();
+                    let token = self.canceller.token();
 
                     Task::future(async move {
                         let result = rt_handle
-                            .spawn(async move {
+                            .spawn(token.run(async move {
                                 // Parse the address
                                 let addr: SocketAddr = match peer_address.parse() {
                                     Ok(addr) => addr,
@@ -307,13 +751,14 @@ impl EmbeddedNode {
                                     )),
                                     Err(e) => NodeMessage::Error(BonsaiNodeError::from(e)),
                                 }
-                            })
+                            }))
                             .await;
 
                         result.unwrap_or_else(|e| {
-                            NodeMessage::Error(BonsaiNodeError::Generic(e.to_string()))
+                            Some(NodeMessage::Error(BonsaiNodeError::Generic(e.to_string())))
                         })
                     })
+                    .and_then(Task::done)
                 } else {
                     Task::done(NodeMessage::Error(BonsaiNodeError::Generic(
                         "Node not running".to_string(),
@@ -331,22 +776,24 @@ impl EmbeddedNode {
                 if let Some(handle) = &self.handle {
                     let handle = handle.clone();
                     let rt_handle = Handle::current();
+                    let token = self.canceller.token();
 
                     Task::future(async move {
                         let result = rt_handle
-                            .spawn(async move {
+                            .spawn(token.run(async move {
                                 let node = handle.read().await;
                                 match node.disconnect_peer(&socket).await {
                                     Ok(_) => NodeMessage::PeerDisconnected(socket),
                                     Err(e) => NodeMessage::Error(BonsaiNodeError::from(e)),
                                 }
-                            })
+                            }))
                             .await;
 
                         result.unwrap_or_else(|e| {
-                            NodeMessage::Error(BonsaiNodeError::Generic(e.to_string()))
+                            Some(NodeMessage::Error(BonsaiNodeError::Generic(e.to_string())))
                         })
                     })
+                    .and_then(Task::done)
                 } else {
                     Task::done(NodeMessage::Error(BonsaiNodeError::Generic(
                         "Node not running".to_string(),
@@ -369,9 +816,71 @@ impl EmbeddedNode {
             }
             NodeMessage::ClearLogs => {
                 self.log_capture.clear();
+                self.log_view.selected.clear();
+                self.log_view.matching.clear();
+
+                Task::none()
+            }
+            NodeMessage::LogLevelToggled(level) => {
+                let hidden_levels = &mut self.log_view.filter.hidden_levels;
+                if !hidden_levels.remove(&level) {
+                    hidden_levels.insert(level);
+                }
+                self.log_view.refilter(&self.log_capture);
+
+                Task::none()
+            }
+            NodeMessage::LogTargetChanged(target) => {
+                self.log_view.filter.target = target;
+                self.log_view.refilter(&self.log_capture);
+
+                Task::none()
+            }
+            NodeMessage::LogSearchChanged(search) => {
+                self.log_view.filter.search = search;
+                self.log_view.refilter(&self.log_capture);
+
+                Task::none()
+            }
+            NodeMessage::ToggleLogsPaused => {
+                self.log_view.paused_at = match self.log_view.paused_at {
+                    Some(_) => None,
+                    None => Some(self.log_capture.next_seq()),
+                };
+
+                Task::none()
+            }
+            NodeMessage::LogsScrolled(viewport) => {
+                // The panel is anchored to the bottom, so offsets count from it.
+                self.log_view.scroll_from_bottom = viewport.absolute_offset().y;
+                self.log_view.viewport_height = viewport.bounds().height;
+
+                Task::none()
+            }
+            NodeMessage::ToggleLogSelected(seq) => {
+                if !self.log_view.selected.remove(&seq) {
+                    self.log_view.selected.insert(seq);
+                }
 
                 Task::none()
             }
+            NodeMessage::CopySelectedLogs => {
+                let selected: Vec<String> = self
+                    .log_capture
+                    .get(self.log_view.selected.iter().copied())
+                    .iter()
+                    .map(|log| log.to_string())
+                    .collect();
+                self.log_view.selected.clear();
+
+                if selected.is_empty() {
+                    Task::none()
+                } else {
+                    clipboard::write(selected.join("\n"))
+                }
+            }
+            // The bundle needs the settings, so `Bonsai` exports it.
+            NodeMessage::ExportDiagnostics | NodeMessage::DiagnosticsExported(_) => Task::none(),
             NodeMessage::BlockHeightInputChanged(value) => {
                 let clean = value.replace(",", "");
 
@@ -394,10 +903,11 @@ impl EmbeddedNode {
                 if let Some(handle) = &self.handle {
                     let handle = handle.clone();
                     let rt_handle = Handle::current();
+                    let token = self.canceller.token();
 
                     Task::future(async move {
                         let result = rt_handle
-                            .spawn(async move {
+                            .spawn(token.run(async move {
                                 let node = handle.read().await;
 
                                 let blockhash = match node.get_blockhash(height as u32) {
@@ -425,11 +935,12 @@ impl EmbeddedNode {
                                         NodeMessage::Error(BonsaiNodeError::from(e))
                                     }
                                 }
-                            })
+                            }))
                             .await;
 
-                        result.unwrap_or(NodeMessage::BlockFetched(None))
+                        result.unwrap_or(Some(NodeMessage::BlockFetched(None)))
                     })
+                    .and_then(Task::done)
                 } else {
                     Task::none()
                 }
@@ -459,52 +970,103 @@ impl EmbeddedNode {
         }
     }
 
-    pub(crate) fn subscribe(&self) -> Subscription<NodeMessage> {
-        let tick_subscription =
-            iced::time::every(Duration::from_millis(32)).map(|_| NodeMessage::Tick);
+    /// Gracefully shut down the node, if it is running, through [`shutdown`].
+    pub fn begin_shutdown(&mut self, restart: bool) -> Task<NodeMessage> {
+        self.status = NodeStatus::ShuttingDown;
+        self.subscription_active = false;
+        self.is_shutting_down = true;
+        self.start_time = None;
+        self.restart_after_shutdown = restart;
+        self.block_receiver = None;
+        self.sync_history.stopped();
+
+        if let Some(stats) = &mut self.statistics {
+            stats.peer_informations.clear();
+        }
+
+        match self.handle.take() {
+            Some(node_handle) => {
+                let canceller = std::mem::take(&mut self.canceller);
+                Task::run(shutdown(node_handle, canceller), |message| message)
+            }
+            None => Task::done(NodeMessage::ShutdownFinished(Ok(()))),
+        }
+    }
+
+    /// Run a server until the node shuts down, reporting its failure as a node error.
+    fn spawn_server(
+        &self,
+        server: impl Future<Output = Result<(), BonsaiNodeError>> + Send + 'static,
+    ) -> Task<NodeMessage> {
+        Task::future(self.canceller.token().run(server)).and_then(|result| match result {
+            Err(e) => Task::done(NodeMessage::Error(e)),
+            Ok(()) => Task::none(),
+        })
+    }
 
-        let mut subscriptions = vec![tick_subscription];
+    /// Publish the peers that connected or disconnected since the last statistics.
+    fn publish_peer_events(&self, stats: &NodeStatistics) {
+        let previous = self
+            .statistics
+            .as_ref()
+            .map(|s| s.peer_informations.as_slice())
+            .unwrap_or_default();
+
+        for peer in &stats.peer_informations {
+            if !previous.iter().any(|p| p.socket == peer.socket) {
+                self.events.publish(Event::PeerConnected {
+                    address: peer.socket,
+                    user_agent: peer.user_agent.clone(),
+                });
+            }
+        }
+        for peer in previous {
+            if !stats
+                .peer_informations
+                .iter()
+                .any(|p| p.socket == peer.socket)
+            {
+                self.events.publish(Event::PeerDisconnected {
+                    address: peer.socket,
+                });
+            }
+        }
+    }
+
+    /// Background node events: statistics and new blocks.
+    ///
+    /// Runs regardless of the active tab.
+    pub fn subscribe(&self) -> Subscription<NodeMessage> {
+        let mut subscriptions = vec![self.block_subscription()];
 
         if self.subscription_active {
             subscriptions.push(
                 iced::time::every(Duration::from_secs(FETCH_STATISTICS_TIME))
                     .map(|_| NodeMessage::GetStatistics),
             );
-
-            subscriptions.push(Self::block_subscription());
+            subscriptions.push(
+                iced::time::every(Duration::from_secs(PROXY_CHECK_INTERVAL))
+                    .map(|_| NodeMessage::CheckProxy),
+            );
         }
 
         Subscription::batch(subscriptions)
     }
 
-    pub fn unsubscribe(&mut self) {
-        self.subscription_active = false;
+    /// Events only needed while a node tab is visible.
+    pub fn view_subscription(&self) -> Subscription<NodeMessage> {
+        iced::time::every(Duration::from_millis(32)).map(|_| NodeMessage::Tick)
     }
 
-    fn block_subscription() -> Subscription<NodeMessage> {
-        Subscription::run(|| {
-            iced::stream::channel(
-                100,
-                |mut output: iced::futures::channel::mpsc::Sender<NodeMessage>| async move {
-                    loop {
-                        let mut receiver_guard = BLOCK_RECEIVER.lock().await;
-
-                        if let Some(receiver) = receiver_guard.as_mut()
-                            && let Some(block) = receiver.recv().await
-                        {
-                            let _ = output.send(NodeMessage::NewBlock(block)).await;
-                            continue;
-                        }
-
-                        drop(receiver_guard);
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                    }
-                },
-            )
-        })
+    /// Blocks validated by the running node, keyed by the node instance.
+    fn block_subscription(&self) -> Subscription<NodeMessage> {
+        match &self.block_receiver {
+            Some(block_receiver) => Subscription::run_with(block_receiver.clone(), block_stream),
+            None => Subscription::none(),
+        }
     }
 
-    pub(crate) fn view_tab(
+    pub fn view_tab(
         &self,
         tab: Tab,
         app_clock: usize,
@@ -526,11 +1088,16 @@ impl EmbeddedNode {
             &self.status,
             &self.statistics,
             &self.log_capture,
+            &self.log_view,
+            &self.shutdown_progress,
+            &self.supervisor,
+            &self.sync_history,
+            &self.proxy_status,
             app_clock,
         )
     }
 
-    pub(crate) fn view_network(&self) -> Element<'_, NodeMessage> {
+    pub fn view_network(&self) -> Element<'_, NodeMessage> {
         use crate::node::network::view;
         view::view_p2p(
             &self.status,
@@ -540,12 +1107,12 @@ impl EmbeddedNode {
         )
     }
 
-    pub(crate) fn view_utreexo(&self) -> Element<'_, NodeMessage> {
+    pub fn view_utreexo(&self) -> Element<'_, NodeMessage> {
         use crate::node::utreexo::view;
         view::view_utreexo(&self.statistics, &self.accumulator_qr_data)
     }
 
-    pub(crate) fn view_blocks(&self) -> Element<'_, NodeMessage> {
+    pub fn view_blocks(&self) -> Element<'_, NodeMessage> {
         use crate::node::blocks::view;
         view::view_blocks(
             &self.block_explorer_height_str,
@@ -556,42 +1123,71 @@ impl EmbeddedNode {
     }
 }
 
-pub(crate) async fn start_node(
+/// Stream the blocks of a [`BlockReceiver`], until its node drops the sending end.
+fn block_stream(block_receiver: &BlockReceiver) -> impl Stream<Item = NodeMessage> + use<> {
+    let receiver = block_receiver.receiver.clone();
+
+    iced::stream::channel(
+        BLOCK_CHANNEL_CAPACITY,
+        move |mut output: iced::futures::channel::mpsc::Sender<NodeMessage>| async move {
+            let mut receiver = receiver.lock().await;
+
+            while let Some(block) = receiver.recv().await {
+                if output.send(NodeMessage::NewBlock(block)).await.is_err() {
+                    break;
+                }
+            }
+        },
+    )
+}
+
+/// The delay before the `attempt`-th automatic restart of a failed node.
+fn retry_delay(attempt: u32) -> Duration {
+    let delay = RETRY_BASE_DELAY.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    Duration::from_secs(delay.min(RETRY_MAX_DELAY))
+}
+
+pub async fn start_node(
     node_config: UtreexoNodeConfig,
-) -> Result<Arc<RwLock<Node>>, String> {
+    fee_estimator: FeeEstimator,
+    events: EventPublisher,
+) -> Result<(Arc<RwLock<Node>>, BlockReceiver), BonsaiNodeError> {
     let rt_handle = Handle::current();
 
     rt_handle
-        .spawn(async {
-            let node = Builder::new()
-                .from_config(node_config)
-                .build()
-                .await
-                .map_err(|e| e.to_string())?;
-
-            let (block_tx, block_rx) = mpsc::unbounded_channel();
-            let forwarder = Arc::new(BlockForwarder { tx: block_tx });
+        .spawn(async move {
+            // Before building, so a second instance never opens the chain state.
+            let datadir_lock = DataDirLock::acquire(&PathBuf::from(&node_config.datadir))?;
+            let node = Builder::new().from_config(node_config).build().await?;
+
+            let (block_tx, block_rx) = mpsc::channel(BLOCK_CHANNEL_CAPACITY);
+            let forwarder = Arc::new(BlockForwarder {
+                tx: block_tx,
+                fee_estimator,
+                events,
+                tip: StdMutex::new(None),
+                caught_up: AtomicBool::new(false),
+                _datadir_lock: datadir_lock,
+            });
 
             node.block_subscriber(forwarder);
 
-            // Store receiver globally
-            *BLOCK_RECEIVER.lock().await = Some(block_rx);
+            // The receiver belongs to this node instance only.
+            let block_receiver = BlockReceiver {
+                node_id: NEXT_NODE_ID.fetch_add(1, Ordering::Relaxed),
+                receiver: Arc::new(Mutex::new(block_rx)),
+            };
 
-            Ok(Arc::new(RwLock::new(node)))
+            Ok::<_, BonsaiNodeError>((Arc::new(RwLock::new(node)), block_receiver))
         })
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(|e| BonsaiNodeError::Generic(e.to_string()))?
 }
 
-pub(crate) async fn stop_node(handle: Arc<RwLock<Node>>) -> Result<(), String> {
-    match Arc::try_unwrap(handle) {
-        Ok(lock) => {
-            let node = lock.into_inner();
-            node.shutdown().await.map_err(|e| e.to_string())
-        }
-        Err(arc) => {
-            let count = Arc::strong_count(&arc);
-            Err(format!("Cannot shutdown: {} references remain", count))
-        }
-    }
+/// Stop a node that no task holds a handle to anymore.
+pub async fn stop_node(node: RwLock<Node>) -> Result<(), String> {
+    node.into_inner()
+        .shutdown()
+        .await
+        .map_err(|e| e.to_string())
 }
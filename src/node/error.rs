@@ -3,7 +3,7 @@ use bdk_floresta::error::NodeError;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
-pub(crate) enum BonsaiNodeError {
+pub enum BonsaiNodeError {
     #[error("Generic Error: {0}")]
     Generic(String),
 
@@ -12,6 +12,22 @@ pub(crate) enum BonsaiNodeError {
 
     #[error(transparent)]
     NodeExecError(#[from] NodeError),
+
+    #[error("Shutdown Timeout: {0} tasks still hold the node")]
+    ShutdownTimeout(usize),
+
+    #[error("Data Directory Locked: {datadir} is in use by {}", lock_holder(.pid))]
+    DataDirLocked { datadir: String, pid: Option<u32> },
+
+    #[error("Node Stalled: no progress for {0} minutes while behind its peers")]
+    Stalled(u64),
+}
+
+fn lock_holder(pid: &Option<u32>) -> String {
+    match pid {
+        Some(pid) => format!("process {pid}"),
+        None => "another process".to_string(),
+    }
 }
 
 impl From<String> for BonsaiNodeError {
@@ -19,3 +35,69 @@ impl From<String> for BonsaiNodeError {
         BonsaiNodeError::Generic(s)
     }
 }
+
+impl BonsaiNodeError {
+    /// A human explanation of the failure, shown alongside the error.
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            Self::NodeBuildError(_) => self.os_explanation().unwrap_or(
+                "The node could not be started with the current configuration. Check the settings and the data directory.",
+            ),
+            Self::NodeExecError(_) => self
+                .os_explanation()
+                .unwrap_or("The node stopped responding while running."),
+            Self::Generic(_) => self
+                .os_explanation()
+                .unwrap_or("The node ran into an unexpected error."),
+            Self::ShutdownTimeout(_) => {
+                "Background tasks did not release the node in time, so it could not be stopped."
+            }
+            Self::Stalled(_) => {
+                "The node stopped validating blocks, although its peers could serve them."
+            }
+            Self::DataDirLocked { .. } => {
+                "Another Bonsai instance is running on this data directory."
+            }
+        }
+    }
+
+    /// An explanation of a well-known OS error, for errors that only carry its message.
+    fn os_explanation(&self) -> Option<&'static str> {
+        let message = self.to_string().to_lowercase();
+
+        if message.contains("lock file") || message.contains("resource temporarily unavailable") {
+            Some("The data directory is in use, probably by another running instance.")
+        } else if message.contains("permission denied") {
+            Some("The data directory can not be written to. Check its permissions.")
+        } else if message.contains("address already in use") {
+            Some("A port the node listens on is already taken by another process.")
+        } else if message.contains("no space left on device") {
+            Some("The disk holding the data directory is full.")
+        } else {
+            None
+        }
+    }
+
+    /// Whether the node itself stops working with this error, rather than a single operation.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Self::NodeBuildError(_)
+                | Self::ShutdownTimeout(_)
+                | Self::DataDirLocked { .. }
+                | Self::Stalled(_)
+        )
+    }
+
+    /// Whether a node that failed with this error may work after a restart.
+    pub fn is_retryable(&self) -> bool {
+        // A locked data directory stays locked until the other instance exits.
+        self.is_restartable() && !matches!(self, Self::DataDirLocked { .. })
+    }
+
+    /// Whether it is safe to restart a node that failed with this error.
+    pub fn is_restartable(&self) -> bool {
+        // A node that could not be stopped may still be running.
+        !matches!(self, Self::ShutdownTimeout(_))
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use bitcoin::Block;
+use bitcoin::BlockHash;
+use bitcoin::SignedAmount;
+use bitcoin::Txid;
+use bitcoin::consensus::encode::serialize_hex;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::node::error::BonsaiNodeError;
+use crate::node::shutdown::CancellationToken;
+
+/// Whether the event socket is enabled by default.
+pub const EVENT_SOCKET: bool = false;
+
+/// The event socket, created in the network's data directory while the node runs.
+pub const EVENT_SOCKET_FILE: &str = "events.sock";
+
+/// How many events can be waiting for a slow client before it misses some.
+const EVENT_CAPACITY: usize = 1000;
+
+/// What a client subscribes to, named like the `topic` of its [`Event`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topic {
+    Block,
+    RawBlock,
+    Reorg,
+    PeerConnected,
+    PeerDisconnected,
+    WalletTx,
+}
+
+/// An event published on the event socket, as a line of JSON tagged with its topic.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "topic", rename_all = "lowercase")]
+pub enum Event {
+    /// A block validated by the node.
+    Block {
+        hash: String,
+        height: u32,
+    },
+    /// A block validated by the node, serialized.
+    RawBlock {
+        height: u32,
+        hex: String,
+    },
+    /// A block at or below the previous tip, which doesn't build on it.
+    Reorg {
+        height: u32,
+        old_tip: String,
+        old_height: u32,
+        new_hash: String,
+    },
+    PeerConnected {
+        address: SocketAddr,
+        user_agent: String,
+    },
+    PeerDisconnected {
+        address: SocketAddr,
+    },
+    /// A wallet transaction seen in a block validated by the node.
+    WalletTx {
+        txid: String,
+        height: u32,
+        net: i64,
+    },
+}
+
+impl Event {
+    pub fn topic(&self) -> Topic {
+        match self {
+            Self::Block { .. } => Topic::Block,
+            Self::RawBlock { .. } => Topic::RawBlock,
+            Self::Reorg { .. } => Topic::Reorg,
+            Self::PeerConnected { .. } => Topic::PeerConnected,
+            Self::PeerDisconnected { .. } => Topic::PeerDisconnected,
+            Self::WalletTx { .. } => Topic::WalletTx,
+        }
+    }
+
+    pub fn block(block: &Block, height: u32) -> Self {
+        Self::Block {
+            hash: block.block_hash().to_string(),
+            height,
+        }
+    }
+
+    pub fn raw_block(block: &Block, height: u32) -> Self {
+        Self::RawBlock {
+            height,
+            hex: serialize_hex(block),
+        }
+    }
+
+    pub fn reorg(height: u32, old_tip: BlockHash, old_height: u32, new_hash: BlockHash) -> Self {
+        Self::Reorg {
+            height,
+            old_tip: old_tip.to_string(),
+            old_height,
+            new_hash: new_hash.to_string(),
+        }
+    }
+
+    pub fn wallet_tx(txid: Txid, height: u32, net: SignedAmount) -> Self {
+        Self::WalletTx {
+            txid: txid.to_string(),
+            height,
+            net: net.to_sat(),
+        }
+    }
+}
+
+/// Publishes [`Event`]s to the clients of the event socket.
+///
+/// Lives as long as the app, so it can be fed from anywhere node events show up.
+#[derive(Clone)]
+pub struct EventPublisher(broadcast::Sender<Event>);
+
+impl Default for EventPublisher {
+    fn default() -> Self {
+        EventPublisher(broadcast::channel(EVENT_CAPACITY).0)
+    }
+}
+
+impl EventPublisher {
+    pub fn publish(&self, event: Event) {
+        // Nobody listening is not an error.
+        let _ = self.0.send(event);
+    }
+
+    /// Whether any client is connected, to skip building events nobody receives.
+    pub fn has_subscribers(&self) -> bool {
+        self.0.receiver_count() > 0
+    }
+}
+
+/// A line sent by a client to change its subscriptions.
+#[derive(Deserialize)]
+struct SubscriptionRequest {
+    #[serde(default)]
+    subscribe: Vec<Topic>,
+    #[serde(default)]
+    unsubscribe: Vec<Topic>,
+}
+
+/// Publish events as newline-delimited JSON on a Unix domain socket at `path`.
+///
+/// Clients receive nothing until they subscribe to some topics, by sending
+/// `{"subscribe": ["block", "wallettx"]}`. Runs until the token is cancelled,
+/// along with every client session, and removes the socket when it stops.
+#[cfg(unix)]
+pub async fn serve(
+    path: PathBuf,
+    publisher: EventPublisher,
+    token: CancellationToken,
+) -> Result<(), BonsaiNodeError> {
+    use tokio::net::UnixListener;
+    use tracing::debug;
+    use tracing::info;
+    use tracing::warn;
+
+    /// Removes the socket file when dropped.
+    struct SocketFile(PathBuf);
+
+    impl Drop for SocketFile {
+        fn drop(&mut self) {
+            if let Err(e) = std::fs::remove_file(&self.0) {
+                warn!("Failed to remove {}: {e}", self.0.to_string_lossy());
+            }
+        }
+    }
+
+    // A socket left behind by a crash would make binding fail.
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).map_err(|e| {
+        BonsaiNodeError::Generic(format!(
+            "Failed to bind event socket to {}: {e}",
+            path.to_string_lossy()
+        ))
+    })?;
+    let _socket_file = SocketFile(path.clone());
+    info!("Publishing events on {}", path.to_string_lossy());
+
+    token
+        .clone()
+        .run(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        debug!("Event socket client connected");
+                        let events = publisher.0.subscribe();
+                        tokio::spawn(token.clone().run(session(stream, events)));
+                    }
+                    Err(e) => warn!("Failed to accept event socket client: {e}"),
+                }
+            }
+        })
+        .await;
+
+    info!("Event socket closed");
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn serve(
+    _path: PathBuf,
+    _publisher: EventPublisher,
+    _token: CancellationToken,
+) -> Result<(), BonsaiNodeError> {
+    Err(BonsaiNodeError::Generic(
+        "The event socket requires Unix domain sockets".to_string(),
+    ))
+}
+
+#[cfg(unix)]
+async fn session(stream: tokio::net::UnixStream, mut events: broadcast::Receiver<Event>) {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::io::BufReader;
+    use tokio::sync::broadcast::error::RecvError;
+    use tracing::debug;
+    use tracing::warn;
+
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut topics: HashSet<Topic> = HashSet::new();
+
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => match serde_json::from_str::<SubscriptionRequest>(&line) {
+                    Ok(request) => {
+                        topics.extend(request.subscribe);
+                        for topic in request.unsubscribe {
+                            topics.remove(&topic);
+                        }
+                        None
+                    }
+                    Err(e) => Some(serde_json::json!({ "error": e.to_string() }).to_string()),
+                },
+                _ => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) if topics.contains(&event.topic()) => serde_json::to_string(&event).ok(),
+                Ok(_) => None,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Event socket client missed {missed} events");
+                    None
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        if let Some(mut line) = line {
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    debug!("Event socket client disconnected");
+}
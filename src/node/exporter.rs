@@ -0,0 +1,238 @@
+use core::fmt::Write;
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use bdk_floresta::ConnectionKind;
+use bdk_floresta::TransportProtocol;
+use bitcoin::Network;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::node::error::BonsaiNodeError;
+use crate::node::shutdown::WeakNode;
+use crate::node::stats_fetcher::NodeStatistics;
+use crate::node::stats_fetcher::collect_stats;
+
+/// Whether the metrics exporter is enabled by default.
+pub const METRICS_SERVER: bool = false;
+
+/// The default port of the metrics exporter.
+pub const METRICS_PORT: u16 = 9332;
+
+/// The content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Clone)]
+struct ExporterState {
+    node: WeakNode,
+    network: Network,
+    start_time: Instant,
+}
+
+/// Export the node's statistics on `/metrics`, in the Prometheus text format,
+/// on a localhost port.
+///
+/// Statistics are gathered on every scrape, with the uptime counted from `start_time`,
+/// when the node started. Runs until dropped.
+pub async fn serve(
+    node: WeakNode,
+    network: Network,
+    port: u16,
+    start_time: Instant,
+) -> Result<(), BonsaiNodeError> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let listener = TcpListener::bind(address).await.map_err(|e| {
+        BonsaiNodeError::Generic(format!("Failed to bind metrics exporter to {address}: {e}"))
+    })?;
+    info!("Exporting metrics on http://{address}/metrics");
+
+    let state = ExporterState {
+        node,
+        network,
+        start_time,
+    };
+    let app = Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(state);
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| BonsaiNodeError::Generic(format!("Metrics exporter stopped: {e}")))
+}
+
+async fn metrics(State(state): State<ExporterState>) -> Response {
+    let handle = match state.node.upgrade() {
+        Ok(handle) => handle,
+        Err(e) => return (StatusCode::SERVICE_UNAVAILABLE, format!("{e}\n")).into_response(),
+    };
+
+    match collect_stats(handle, Some(state.start_time)).await {
+        Ok(stats) => (
+            [(header::CONTENT_TYPE, CONTENT_TYPE)],
+            render(&stats, state.network),
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}\n")).into_response(),
+    }
+}
+
+/// Metrics in the Prometheus text exposition format.
+#[derive(Default)]
+struct Exposition(String);
+
+impl Exposition {
+    /// Write a metric family with its samples, each given as labels and a value.
+    fn metric<'a>(
+        &mut self,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Vec<(&'a str, String)>, f64)>,
+    ) {
+        let _ = writeln!(self.0, "# HELP {name} {help}");
+        let _ = writeln!(self.0, "# TYPE {name} {kind}");
+
+        for (labels, value) in samples {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(label, value)| format!("{label}=\"{}\"", escape_label(value)))
+                .collect();
+            if labels.is_empty() {
+                let _ = writeln!(self.0, "{name} {value}");
+            } else {
+                let _ = writeln!(self.0, "{name}{{{}}} {value}", labels.join(","));
+            }
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.metric(name, "gauge", help, [(Vec::new(), value)]);
+    }
+
+    /// A gauge with one sample per value of `label`.
+    fn gauge_by(&mut self, name: &str, help: &str, label: &str, counts: BTreeMap<String, usize>) {
+        self.metric(
+            name,
+            "gauge",
+            help,
+            counts
+                .into_iter()
+                .map(|(value, count)| (vec![(label, value)], count as f64)),
+        );
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn count_by<T>(items: &[T], key: impl Fn(&T) -> String) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for item in items {
+        *counts.entry(key(item)).or_default() += 1;
+    }
+    counts
+}
+
+fn render(stats: &NodeStatistics, network: Network) -> String {
+    let mut exposition = Exposition::default();
+    let peers = &stats.peer_informations;
+
+    exposition.metric(
+        "bonsai_info",
+        "gauge",
+        "Information about the node, in labels.",
+        [(
+            vec![
+                ("network", network.to_core_arg().to_string()),
+                ("user_agent", stats.user_agent.clone()),
+                ("version", env!("CARGO_PKG_VERSION").to_string()),
+            ],
+            1.0,
+        )],
+    );
+    exposition.gauge(
+        "bonsai_blocks",
+        "Height of the last validated block.",
+        stats.blocks as f64,
+    );
+    exposition.gauge(
+        "bonsai_headers",
+        "Height of the best known header.",
+        stats.headers as f64,
+    );
+    exposition.gauge(
+        "bonsai_initial_block_download",
+        "Whether the node is in initial block download.",
+        if stats.in_ibd { 1.0 } else { 0.0 },
+    );
+    exposition.gauge(
+        "bonsai_accumulator_leaves",
+        "Leaves added to the Utreexo accumulator.",
+        stats.accumulator.leaves as f64,
+    );
+    exposition.gauge(
+        "bonsai_accumulator_roots",
+        "Roots of the Utreexo accumulator.",
+        stats.accumulator.roots.len() as f64,
+    );
+    exposition.gauge("bonsai_peers", "Connected peers.", peers.len() as f64);
+    exposition.gauge_by(
+        "bonsai_peers_by_connection_kind",
+        "Connected peers by connection kind.",
+        "kind",
+        count_by(peers, |peer| {
+            match peer.connection_kind {
+                ConnectionKind::Manual => "manual",
+                ConnectionKind::Feeler => "feeler",
+                _ => "regular",
+            }
+            .to_string()
+        }),
+    );
+    exposition.gauge_by(
+        "bonsai_peers_by_transport",
+        "Connected peers by P2P transport protocol.",
+        "transport",
+        count_by(peers, |peer| {
+            match peer.transport_protocol {
+                TransportProtocol::V1 => "v1",
+                TransportProtocol::V2 => "v2",
+            }
+            .to_string()
+        }),
+    );
+    exposition.gauge_by(
+        "bonsai_peers_by_implementation",
+        "Connected peers by node implementation.",
+        "implementation",
+        count_by(peers, |peer| format!("{:?}", peer.node_impl).to_lowercase()),
+    );
+    // A gauge, as it restarts from zero along with the node.
+    exposition.gauge(
+        "bonsai_uptime_seconds",
+        "Seconds since the node started.",
+        stats.uptime.as_secs_f64(),
+    );
+    if let Some(memory) = stats.memory {
+        exposition.gauge(
+            "process_resident_memory_bytes",
+            "Resident memory size in bytes.",
+            memory as f64,
+        );
+    }
+
+    exposition.0
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use bdk_floresta::UtxoData;
+use bitcoin::Amount;
+use bitcoin::Block;
+use bitcoin::FeeRate;
+use bitcoin::OutPoint;
+
+/// How many blocks the [`FeeEstimator`] keeps track of.
+pub const FEE_ESTIMATOR_WINDOW: usize = 144;
+
+/// Feerate statistics of a validated block.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockFeerates {
+    pub height: u32,
+    /// 10th percentile feerate.
+    pub low: FeeRate,
+    /// Median feerate.
+    pub median: FeeRate,
+}
+
+impl BlockFeerates {
+    /// Compute the feerates of a [`Block`] from the UTXOs it spends.
+    ///
+    /// Returns `None` for blocks without any non-coinbase transaction.
+    pub fn from_block(
+        block: &Block,
+        height: u32,
+        spent_utxos: &HashMap<OutPoint, UtxoData>,
+    ) -> Option<Self> {
+        let mut feerates: Vec<FeeRate> = block
+            .txdata
+            .iter()
+            .filter(|tx| !tx.is_coinbase())
+            .filter_map(|tx| {
+                let input_value = tx
+                    .input
+                    .iter()
+                    .map(|input| {
+                        spent_utxos
+                            .get(&input.previous_output)
+                            .map(|utxo| utxo.txout.value)
+                    })
+                    .sum::<Option<Amount>>()?;
+                let output_value: Amount = tx.output.iter().map(|output| output.value).sum();
+                let fee = input_value.checked_sub(output_value)?;
+
+                Some(fee / tx.weight())
+            })
+            .collect();
+
+        if feerates.is_empty() {
+            return None;
+        }
+        feerates.sort();
+
+        Some(BlockFeerates {
+            height,
+            low: feerates[feerates.len() / 10],
+            median: feerates[feerates.len() / 2],
+        })
+    }
+}
+
+/// A feerate estimator fed by blocks validated by the embedded node.
+#[derive(Clone, Default)]
+pub struct FeeEstimator {
+    recent: Arc<Mutex<VecDeque<BlockFeerates>>>,
+}
+
+impl FeeEstimator {
+    pub fn record(&self, feerates: BlockFeerates) {
+        let mut recent = self.recent.lock().unwrap();
+        recent.push_back(feerates);
+        if recent.len() > FEE_ESTIMATOR_WINDOW {
+            recent.pop_front();
+        }
+    }
+
+    /// Feerates of the most recent blocks, oldest first.
+    pub fn recent(&self) -> Vec<BlockFeerates> {
+        self.recent.lock().unwrap().iter().copied().collect()
+    }
+
+    /// The median of the recent blocks' median feerates.
+    pub fn estimate(&self) -> Option<FeeRate> {
+        let mut medians: Vec<FeeRate> = self.recent().iter().map(|f| f.median).collect();
+        medians.sort();
+
+        medians.get(medians.len() / 2).copied()
+    }
+
+    /// The lowest feerate that got confirmed in recent blocks, usually.
+    pub fn low(&self) -> Option<FeeRate> {
+        let mut lows: Vec<FeeRate> = self.recent().iter().map(|f| f.low).collect();
+        lows.sort();
+
+        lows.get(lows.len() / 4).copied()
+    }
+
+    /// Whether the latest block's median feerate is in the
+    /// bottom quartile of the recent blocks' median feerates.
+    pub fn fees_are_low(&self) -> bool {
+        let recent = self.recent();
+        let Some(latest) = recent.last() else {
+            return false;
+        };
+
+        let mut medians: Vec<FeeRate> = recent.iter().map(|f| f.median).collect();
+        medians.sort();
+
+        latest.median <= medians[medians.len() / 4]
+    }
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tracing::info;
+use tracing::warn;
+
+use crate::node::error::BonsaiNodeError;
+
+/// The lock file, in the data directory it protects.
+pub const LOCK_FILE: &str = "bonsai.lock";
+
+/// An exclusive lock on a node's data directory, so only one process opens its chain state.
+///
+/// The operating system releases the lock when the process exits, even by
+/// crashing, so a lock file left behind is stale as soon as nobody holds it.
+/// The file records the holder's PID, to tell users which process to look for.
+pub struct DataDirLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl DataDirLock {
+    /// Lock `datadir`, failing with [`BonsaiNodeError::DataDirLocked`] if another process holds it.
+    pub fn acquire(datadir: &Path) -> Result<Self, BonsaiNodeError> {
+        let path = datadir.join(LOCK_FILE);
+        let io_error = |e: std::io::Error| {
+            BonsaiNodeError::Generic(format!("Failed to open {}: {e}", path.to_string_lossy()))
+        };
+
+        fs::create_dir_all(datadir).map_err(io_error)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut pid = String::new();
+                let _ = file.read_to_string(&mut pid);
+                return Err(BonsaiNodeError::DataDirLocked {
+                    datadir: datadir.to_string_lossy().to_string(),
+                    pid: pid.trim().parse().ok(),
+                });
+            }
+            // Some filesystems don't support locking, which shouldn't keep the node from running.
+            Err(TryLockError::Error(e)) => {
+                warn!("Failed to lock {}: {e}", path.to_string_lossy());
+            }
+        }
+
+        // The previous holder is gone if it didn't clear the file.
+        let mut previous = String::new();
+        if file.read_to_string(&mut previous).is_ok() && !previous.trim().is_empty() {
+            info!(
+                "Removing stale lock of process {} on {}",
+                previous.trim(),
+                datadir.to_string_lossy()
+            );
+        }
+
+        file.set_len(0).map_err(io_error)?;
+        file.rewind().map_err(io_error)?;
+        write!(file, "{}", std::process::id()).map_err(io_error)?;
+
+        Ok(DataDirLock { file, path })
+    }
+
+    /// Whether `datadir` can be locked, without keeping the lock.
+    pub fn check(datadir: &Path) -> Result<(), BonsaiNodeError> {
+        Self::acquire(datadir).map(drop)
+    }
+}
+
+impl Drop for DataDirLock {
+    fn drop(&mut self) {
+        // Clear the PID before unlocking, so the file isn't mistaken for a stale lock.
+        // It is never removed, since another process may already be waiting on it.
+        if let Err(e) = self.file.set_len(0) {
+            warn!("Failed to clear {}: {e}", self.path.to_string_lossy());
+        }
+        let _ = self.file.unlock();
+    }
+}
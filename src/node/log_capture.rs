@@ -1,51 +1,265 @@
+use std::collections::BTreeSet;
 use std::collections::VecDeque;
-use std::fmt::Write;
+use std::fmt::Display;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use chrono::DateTime;
+use chrono::Local;
+use tracing::Level;
 use tracing::Subscriber;
 use tracing_subscriber::Layer;
 
+/// A captured log event.
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    /// Increases with every record captured, so it identifies a record across clears.
+    pub seq: u64,
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    /// Fields other than the message, in the order they were recorded.
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} {}: {}",
+            self.time.format("%H:%M:%S"),
+            self.level,
+            self.target,
+            self.message
+        )?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How many records are captured before the oldest are dropped.
+pub const LOG_CAPACITY: usize = 50_000;
+
+/// A fixed-capacity ring of captured records, read incrementally by sequence number.
 #[derive(Clone, Default)]
-pub struct LogCapture {
-    logs: Arc<Mutex<VecDeque<String>>>,
-    version: Arc<Mutex<usize>>,
-    max_logs: usize,
+pub struct LogCapture(Arc<Mutex<LogRing>>);
+
+#[derive(Default)]
+struct LogRing {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    next_seq: u64,
+    version: usize,
+}
+
+impl LogRing {
+    /// The sequence number of the oldest record kept.
+    fn first_seq(&self) -> u64 {
+        self.next_seq - self.records.len() as u64
+    }
+
+    fn get(&self, seq: u64) -> Option<&LogRecord> {
+        let index = seq.checked_sub(self.first_seq())?;
+        self.records.get(index as usize)
+    }
 }
 
 impl LogCapture {
-    pub fn new(max_logs: usize) -> Self {
-        Self {
-            logs: Arc::new(Mutex::new(VecDeque::new())),
-            version: Arc::new(Mutex::new(0)),
-            max_logs,
-        }
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(LogRing {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+            ..LogRing::default()
+        })))
     }
 
     pub fn version(&self) -> usize {
-        *self.version.lock().unwrap()
+        self.0.lock().unwrap().version
     }
 
-    pub fn add_log(&self, log: String) {
-        let mut logs = self.logs.lock().unwrap();
-        logs.push_back(log);
-        if logs.len() > self.max_logs {
-            logs.pop_front();
+    /// Capture a record, assigning its sequence number.
+    pub fn add_log(&self, mut log: LogRecord) {
+        let mut ring = self.0.lock().unwrap();
+        log.seq = ring.next_seq;
+        ring.next_seq += 1;
+
+        if ring.records.len() >= ring.capacity {
+            ring.records.pop_front();
+        }
+        if ring.capacity > 0 {
+            ring.records.push_back(log);
         }
-        drop(logs);
+        ring.version = ring.version.wrapping_add(1);
+    }
+
+    /// The sequence number of the next record captured.
+    pub fn next_seq(&self) -> u64 {
+        self.0.lock().unwrap().next_seq
+    }
+
+    /// The sequence number of the oldest record kept.
+    pub fn first_seq(&self) -> u64 {
+        self.0.lock().unwrap().first_seq()
+    }
 
-        let mut version = self.version.lock().unwrap();
-        *version = version.wrapping_add(1);
+    /// The records captured from `seq` on, or all of them if older ones were dropped.
+    pub fn read_since(&self, seq: u64) -> Vec<LogRecord> {
+        let ring = self.0.lock().unwrap();
+        let skip = seq.saturating_sub(ring.first_seq()) as usize;
+        ring.records.iter().skip(skip).cloned().collect()
     }
 
-    pub fn get_logs(&self) -> Vec<String> {
-        self.logs.lock().unwrap().iter().cloned().collect()
+    /// The records with the given sequence numbers, skipping dropped ones.
+    pub fn get(&self, seqs: impl IntoIterator<Item = u64>) -> Vec<LogRecord> {
+        let ring = self.0.lock().unwrap();
+        seqs.into_iter()
+            .filter_map(|seq| ring.get(seq).cloned())
+            .collect()
     }
 
     pub fn clear(&self) {
-        self.logs.lock().unwrap().clear();
+        let mut ring = self.0.lock().unwrap();
+        ring.records.clear();
+        ring.version = ring.version.wrapping_add(1);
+    }
+}
+
+/// The log sources the log panel can be narrowed to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogTarget {
+    #[default]
+    All,
+    Bonsai,
+    FlorestaChain,
+    FlorestaWire,
+}
+
+impl LogTarget {
+    pub const TARGETS: [LogTarget; 4] = [
+        LogTarget::All,
+        LogTarget::Bonsai,
+        LogTarget::FlorestaChain,
+        LogTarget::FlorestaWire,
+    ];
+
+    fn matches(&self, target: &str) -> bool {
+        let prefix = match self {
+            Self::All => return true,
+            Self::Bonsai => "bonsai",
+            Self::FlorestaChain => "floresta_chain",
+            Self::FlorestaWire => "floresta_wire",
+        };
+
+        target.starts_with(prefix)
+    }
+}
 
-        *self.version.lock().unwrap() += 1;
+impl Display for LogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "ALL"),
+            Self::Bonsai => write!(f, "BONSAI"),
+            Self::FlorestaChain => write!(f, "CHAIN"),
+            Self::FlorestaWire => write!(f, "WIRE"),
+        }
+    }
+}
+
+/// Which captured records the log panel shows.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    pub hidden_levels: BTreeSet<Level>,
+    pub target: LogTarget,
+    /// Matched case-insensitively against the message, target and fields.
+    pub search: String,
+}
+
+impl LogFilter {
+    pub fn matches(&self, log: &LogRecord) -> bool {
+        if self.hidden_levels.contains(&log.level) || !self.target.matches(&log.target) {
+            return false;
+        }
+        if self.search.is_empty() {
+            return true;
+        }
+
+        let search = self.search.to_lowercase();
+        log.message.to_lowercase().contains(&search)
+            || log.target.to_lowercase().contains(&search)
+            || log.fields.iter().any(|(name, value)| {
+                name.contains(&search) || value.to_lowercase().contains(&search)
+            })
+    }
+}
+
+/// The state of the log panel.
+///
+/// Only the sequence numbers of records passing the filter are kept, updated
+/// with the records captured since the last [`LogView::sync`], so rendering
+/// fetches just the rows on screen.
+pub struct LogView {
+    pub filter: LogFilter,
+    /// While paused, records from this sequence number on are hidden,
+    /// so new records don't move the panel.
+    pub paused_at: Option<u64>,
+    /// Records selected for copying, by sequence number.
+    pub selected: BTreeSet<u64>,
+    /// Records passing the filter, oldest first.
+    pub matching: VecDeque<u64>,
+    /// The next record to filter.
+    synced: u64,
+    /// How far the panel is scrolled up from the newest record, in pixels.
+    pub scroll_from_bottom: f32,
+    pub viewport_height: f32,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            filter: LogFilter::default(),
+            paused_at: None,
+            selected: BTreeSet::new(),
+            matching: VecDeque::new(),
+            synced: 0,
+            scroll_from_bottom: 0.0,
+            // Until the panel reports its size, assume it is tall.
+            viewport_height: 1000.0,
+        }
+    }
+}
+
+impl LogView {
+    /// Filter the records captured since the last sync.
+    pub fn sync(&mut self, capture: &LogCapture) {
+        let until = self.paused_at.unwrap_or(u64::MAX);
+        if self.synced < until {
+            for log in capture.read_since(self.synced) {
+                if log.seq >= until {
+                    break;
+                }
+                if self.filter.matches(&log) {
+                    self.matching.push_back(log.seq);
+                }
+                self.synced = log.seq + 1;
+            }
+        }
+
+        // Forget records the capture dropped.
+        let first_seq = capture.first_seq();
+        while self.matching.front().is_some_and(|seq| *seq < first_seq) {
+            self.matching.pop_front();
+        }
+    }
+
+    /// Filter every captured record again, after the filter changed.
+    pub fn refilter(&mut self, capture: &LogCapture) {
+        self.matching.clear();
+        self.synced = 0;
+        self.sync(capture);
     }
 }
 
@@ -65,38 +279,42 @@ impl<S: Subscriber> Layer<S> for LogCaptureLayer {
         event: &tracing::Event<'_>,
         _ctx: tracing_subscriber::layer::Context<'_, S>,
     ) {
-        let mut message = String::new();
-
-        let now = chrono::Local::now();
-        write!(&mut message, "[{}] ", now.format("%H:%M:%S")).ok();
-        write!(&mut message, "{} ", event.metadata().level()).ok();
-
-        let mut visitor = MessageVisitor(String::new());
+        let mut visitor = RecordVisitor::default();
         event.record(&mut visitor);
 
-        if visitor.0.is_empty() {
-            write!(&mut message, "{:?}", event).ok();
-        } else {
-            message.push_str(&visitor.0);
-        }
-
-        self.capture.add_log(message);
+        let metadata = event.metadata();
+        self.capture.add_log(LogRecord {
+            seq: 0,
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
     }
 }
 
-struct MessageVisitor(String);
+#[derive(Default)]
+struct RecordVisitor {
+    message: String,
+    fields: Vec<(&'static str, String)>,
+}
 
-impl tracing::field::Visit for MessageVisitor {
+impl tracing::field::Visit for RecordVisitor {
     fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{:?}", value);
         if field.name() == "message" {
-            let debug_str = format!("{:?}", value);
-            self.0 = debug_str.trim_matches('"').to_string();
+            self.message = value.trim_matches('"').to_string();
+        } else {
+            self.fields.push((field.name(), value));
         }
     }
 
     fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
         if field.name() == "message" {
-            self.0 = value.to_string();
+            self.message = value.to_string();
+        } else {
+            self.fields.push((field.name(), value.to_string()));
         }
     }
 }
@@ -1,30 +1,49 @@
 use core::fmt::Debug;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use bdk_floresta::Node;
 use bdk_floresta::UtreexoNodeConfig;
 use bitcoin::Block;
+use iced::widget::scrollable::Viewport;
 use tokio::sync::RwLock;
+use tracing::Level;
 
+use crate::node::control::BlockReceiver;
 use crate::node::error::BonsaiNodeError;
+use crate::node::log_capture::LogTarget;
+use crate::node::proxy::ProxyStatus;
+use crate::node::shutdown::ShutdownProgress;
 use crate::node::stats_fetcher::NodeStatistics;
 
 #[derive(Clone)]
-pub(crate) enum NodeMessage {
+pub enum NodeMessage {
     #[allow(unused)]
     Start,
     Restart,
     Starting,
-    Running(Arc<RwLock<Node>>),
+    Running(Arc<RwLock<Node>>, BlockReceiver),
     ConfigUsed(UtreexoNodeConfig),
     Shutdown,
-    ShuttingDown,
     ShutdownComplete,
+    ShutdownProgress(ShutdownProgress),
+    ShutdownFinished(Result<(), BonsaiNodeError>),
     Tick,
     GetStatistics,
     Statistics(NodeStatistics),
+    CheckProxy,
+    ProxyChecked(ProxyStatus),
     ClearLogs,
+    LogLevelToggled(Level),
+    LogTargetChanged(LogTarget),
+    LogSearchChanged(String),
+    ToggleLogsPaused,
+    LogsScrolled(Viewport),
+    ToggleLogSelected(u64),
+    CopySelectedLogs,
+    ExportDiagnostics,
+    DiagnosticsExported(Result<PathBuf, String>),
     AddPeerInputChanged(String),
     AddPeer,
     PeerConnected(String),
@@ -38,6 +57,8 @@ pub(crate) enum NodeMessage {
     NewBlock(Block),
     ToggleTransactionExpandedIdx(usize),
     Error(BonsaiNodeError),
+    Failed(BonsaiNodeError),
+    Retry(u32),
 }
 
 impl Debug for NodeMessage {
@@ -46,15 +67,33 @@ impl Debug for NodeMessage {
             Self::Start => write!(f, "Start Node"),
             Self::Restart => write!(f, "Restart"),
             Self::Starting => write!(f, "Node Starting"),
-            Self::Running(_) => write!(f, "Node Running"),
+            Self::Running(..) => write!(f, "Node Running"),
             Self::ConfigUsed(_) => write!(f, "Config Used"),
             Self::Shutdown => write!(f, "Stop Node"),
-            Self::ShuttingDown => write!(f, "Node Shutting Down..."),
             Self::ShutdownComplete => write!(f, "Shutdown Complete"),
+            Self::ShutdownProgress(progress) => write!(f, "Shutdown Progress ({progress})"),
+            Self::ShutdownFinished(result) => match result {
+                Ok(_) => write!(f, "Shutdown Finished"),
+                Err(e) => write!(f, "Shutdown Finished ({e})"),
+            },
             Self::Tick => write!(f, "Tick"),
             Self::GetStatistics => write!(f, "Get Stats"),
             Self::Statistics(_) => write!(f, "Node Statistics"),
+            Self::CheckProxy => write!(f, "Check Proxy"),
+            Self::ProxyChecked(status) => write!(f, "ProxyChecked({status})"),
             Self::ClearLogs => write!(f, "Clear Logs"),
+            Self::LogLevelToggled(level) => write!(f, "LogLevelToggled({level})"),
+            Self::LogTargetChanged(target) => write!(f, "LogTargetChanged({target})"),
+            Self::LogSearchChanged(search) => write!(f, "LogSearchChanged({search})"),
+            Self::ToggleLogsPaused => write!(f, "ToggleLogsPaused"),
+            Self::LogsScrolled(_) => write!(f, "LogsScrolled"),
+            Self::ToggleLogSelected(seq) => write!(f, "ToggleLogSelected({seq})"),
+            Self::CopySelectedLogs => write!(f, "CopySelectedLogs"),
+            Self::ExportDiagnostics => write!(f, "ExportDiagnostics"),
+            Self::DiagnosticsExported(result) => match result {
+                Ok(path) => write!(f, "DiagnosticsExported({})", path.to_string_lossy()),
+                Err(e) => write!(f, "DiagnosticsExported({e})"),
+            },
             Self::AddPeerInputChanged(peer) => write!(f, "AddPeerInputChanged({peer})"),
             Self::AddPeer => write!(f, "AddPeer"),
             Self::PeerConnected(peer) => write!(f, "PeerConnected({peer})"),
@@ -75,6 +114,8 @@ impl Debug for NodeMessage {
                 write!(f, "ToggleTransactionExpandedIdx({idx})")
             }
             Self::Error(_) => write!(f, "Node Error"),
+            Self::Failed(_) => write!(f, "Node Failed"),
+            Self::Retry(attempt) => write!(f, "Retry({attempt})"),
         }
     }
 }
@@ -1,11 +1,20 @@
-pub(crate) mod blocks;
-pub(crate) mod control;
-pub(crate) mod error;
-pub(crate) mod geoip;
-pub(crate) mod log_capture;
-pub(crate) mod message;
-pub(crate) mod network;
-pub(crate) mod statistics;
-pub(crate) mod stats_fetcher;
-pub(crate) mod style;
-pub(crate) mod utreexo;
+pub mod blocks;
+pub mod control;
+pub mod error;
+pub mod events;
+pub mod exporter;
+pub mod fee_estimator;
+pub mod geoip;
+pub mod lock;
+pub mod log_capture;
+pub mod message;
+pub mod network;
+pub mod proxy;
+pub mod rest;
+pub mod rpc;
+pub mod shutdown;
+pub mod statistics;
+pub mod stats_fetcher;
+pub mod style;
+pub mod sync_history;
+pub mod utreexo;
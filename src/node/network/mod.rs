@@ -1,2 +1,2 @@
-pub(crate) mod style;
-pub(crate) mod view;
+pub mod style;
+pub mod view;
@@ -11,7 +11,7 @@ use crate::common::interface::constants::BORDER_WIDTH;
 use crate::common::interface::shadow::SHADOW_GRAY;
 
 /// Container for displaying a table with peer info.
-pub(crate) fn peer_info_table_container() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn peer_info_table_container() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
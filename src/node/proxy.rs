@@ -0,0 +1,60 @@
+use core::fmt::Display;
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+/// How often the SOCKS5 proxy is checked while the node runs, in seconds.
+pub const PROXY_CHECK_INTERVAL: u64 = 30;
+
+/// How long the proxy has to answer a check.
+const PROXY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether the node's SOCKS5 proxy, such as Tor, accepts connections.
+#[derive(Clone, Debug, Default)]
+pub enum ProxyStatus {
+    /// The node connects to peers directly.
+    #[default]
+    Disabled,
+    Checking(SocketAddr),
+    Reachable(SocketAddr),
+    Unreachable(SocketAddr, String),
+}
+
+impl Display for ProxyStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "DISABLED"),
+            Self::Checking(proxy) => write!(f, "CHECKING [{proxy}]"),
+            Self::Reachable(proxy) => write!(f, "REACHABLE [{proxy}]"),
+            Self::Unreachable(proxy, _) => write!(f, "UNREACHABLE [{proxy}]"),
+        }
+    }
+}
+
+/// Check that `proxy` speaks SOCKS5 without authentication, as the node uses it.
+pub async fn check_proxy(proxy: SocketAddr) -> ProxyStatus {
+    match tokio::time::timeout(PROXY_CHECK_TIMEOUT, socks5_greeting(proxy)).await {
+        Ok(Ok(())) => ProxyStatus::Reachable(proxy),
+        Ok(Err(e)) => ProxyStatus::Unreachable(proxy, e.to_string()),
+        Err(_) => ProxyStatus::Unreachable(proxy, "timed out".to_string()),
+    }
+}
+
+/// Open a SOCKS5 session, without asking the proxy to connect anywhere.
+async fn socks5_greeting(proxy: SocketAddr) -> io::Result<()> {
+    let mut stream = TcpStream::connect(proxy).await?;
+    // Version 5, offering one method: no authentication.
+    stream.write_all(&[0x05, 0x01, 0x00]).await?;
+
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    match reply {
+        [0x05, 0x00] => Ok(()),
+        [0x05, _] => Err(io::Error::other("the proxy requires authentication")),
+        _ => Err(io::Error::other("not a SOCKS5 proxy")),
+    }
+}
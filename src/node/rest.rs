@@ -0,0 +1,278 @@
+use core::fmt::Display;
+use std::net::SocketAddr;
+
+use axum::Json;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use bdk_floresta::Node;
+use bitcoin::Block;
+use bitcoin::BlockHash;
+use bitcoin::Network;
+use bitcoin::block::Header;
+use bitcoin::consensus::encode::serialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::node::error::BonsaiNodeError;
+use crate::node::rpc::block_json;
+use crate::node::rpc::get_blockchain_info;
+use crate::node::rpc::get_header;
+use crate::node::shutdown::NodeNotRunning;
+use crate::node::shutdown::WeakNode;
+
+/// The most headers served by a single `/rest/headers` request, as in Bitcoin Core.
+const MAX_REST_HEADERS: u32 = 2000;
+
+/// The format of a REST response, given by the extension of the last path segment.
+#[derive(Clone, Copy)]
+enum RestFormat {
+    Bin,
+    Hex,
+    Json,
+}
+
+/// A REST error, returned as plain text with its status code, as in Bitcoin Core.
+struct RestError(StatusCode, String);
+
+impl RestError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        RestError(StatusCode::BAD_REQUEST, message.into())
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        RestError(StatusCode::NOT_FOUND, message.into())
+    }
+
+    fn internal(e: impl Display) -> Self {
+        RestError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (self.0, format!("{}\r\n", self.1)).into_response()
+    }
+}
+
+impl From<NodeNotRunning> for RestError {
+    fn from(e: NodeNotRunning) -> Self {
+        RestError(
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("Service temporarily unavailable: {e}"),
+        )
+    }
+}
+
+#[derive(Clone)]
+struct RestState {
+    node: WeakNode,
+    network: Network,
+}
+
+/// Serve a read-only REST interface on `address`, modeled on Bitcoin Core's `/rest/`.
+///
+/// Blocks are not stored by the node, so they are fetched from peers on
+/// every request. Runs until dropped.
+pub async fn serve(
+    node: WeakNode,
+    network: Network,
+    address: SocketAddr,
+) -> Result<(), BonsaiNodeError> {
+    let listener = TcpListener::bind(address).await.map_err(|e| {
+        BonsaiNodeError::Generic(format!("Failed to bind REST server to {address}: {e}"))
+    })?;
+    info!("REST server listening on {address}");
+
+    let app = Router::new()
+        .route("/rest/block/{file}", get(get_block))
+        .route("/rest/headers/{count}/{file}", get(get_headers))
+        .route(
+            "/rest/blockhashbyheight/{file}",
+            get(get_blockhash_by_height),
+        )
+        .route("/rest/chaininfo.json", get(get_chain_info))
+        .with_state(RestState { node, network });
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| BonsaiNodeError::Generic(format!("REST server stopped: {e}")))
+}
+
+/// Split a `<value>.<format>` path segment.
+fn split_format(file: &str) -> Result<(&str, RestFormat), RestError> {
+    let (value, extension) = file.rsplit_once('.').ok_or_else(|| {
+        RestError::not_found("output format not found (available: bin, hex, json)")
+    })?;
+
+    let format = match extension {
+        "bin" => RestFormat::Bin,
+        "hex" => RestFormat::Hex,
+        "json" => RestFormat::Json,
+        _ => {
+            return Err(RestError::not_found(
+                "output format not found (available: bin, hex, json)",
+            ));
+        }
+    };
+
+    Ok((value, format))
+}
+
+fn parse_blockhash(hash: &str) -> Result<BlockHash, RestError> {
+    hash.parse()
+        .map_err(|_| RestError::bad_request(format!("Invalid hash: {hash}")))
+}
+
+/// Respond with raw bytes, their hex, or the JSON built by `json`.
+fn respond(
+    format: RestFormat,
+    bytes: Vec<u8>,
+    json: impl FnOnce() -> Result<Value, RestError>,
+) -> Result<Response, RestError> {
+    Ok(match format {
+        RestFormat::Bin => {
+            ([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response()
+        }
+        RestFormat::Hex => (
+            [(header::CONTENT_TYPE, "text/plain")],
+            format!("{}\n", hex::encode(bytes)),
+        )
+            .into_response(),
+        RestFormat::Json => Json(json()?).into_response(),
+    })
+}
+
+async fn fetch_block(node: &Node, blockhash: BlockHash) -> Result<Block, RestError> {
+    node.get_block(blockhash)
+        .await
+        .map_err(RestError::internal)?
+        .ok_or_else(|| RestError::not_found(format!("{blockhash} not found")))
+}
+
+async fn get_block(
+    State(state): State<RestState>,
+    Path(file): Path<String>,
+) -> Result<Response, RestError> {
+    let (hash, format) = split_format(&file)?;
+    let blockhash = parse_blockhash(hash)?;
+
+    let handle = state.node.upgrade()?;
+    let node = handle.read().await;
+    let block = fetch_block(&node, blockhash).await?;
+    let tip = node.get_validation_height().map_err(RestError::internal)?;
+    let height = node.get_block_height(blockhash).ok().flatten();
+
+    respond(format, serialize(&block), || {
+        Ok(block_json(&block, height, 2, tip))
+    })
+}
+
+async fn get_headers(
+    State(state): State<RestState>,
+    Path((count, file)): Path<(String, String)>,
+) -> Result<Response, RestError> {
+    let (hash, format) = split_format(&file)?;
+    let blockhash = parse_blockhash(hash)?;
+    let count = count
+        .parse::<u32>()
+        .ok()
+        .filter(|count| (1..=MAX_REST_HEADERS).contains(count))
+        .ok_or_else(|| {
+            RestError::bad_request(format!(
+                "Header count is invalid or out of acceptable range (1-{MAX_REST_HEADERS}): {count}"
+            ))
+        })?;
+
+    let handle = state.node.upgrade()?;
+    let node = handle.read().await;
+    let tip = node.get_validation_height().map_err(RestError::internal)?;
+    let best_header = node.get_height().map_err(RestError::internal)?;
+
+    // Like Core, serve nothing for blocks outside of the best chain.
+    let mut headers: Vec<(u32, Header)> = Vec::new();
+    if let Some(start) = node
+        .get_block_height(blockhash)
+        .map_err(RestError::internal)?
+        .filter(|start| node.get_blockhash(*start).ok() == Some(blockhash))
+    {
+        // Headers are read from the node's header chain, which runs ahead of validation.
+        let end = start
+            .saturating_add(count)
+            .min(best_header.saturating_add(1));
+        for height in start..end {
+            let header = get_header(&node, height).map_err(RestError::internal)?;
+            headers.push((height, header));
+        }
+    }
+
+    let bytes = headers
+        .iter()
+        .flat_map(|(_, header)| serialize(header))
+        .collect();
+    respond(format, bytes, || {
+        Ok(Value::Array(
+            headers
+                .iter()
+                .map(|(height, header)| header_json(header, *height, tip))
+                .collect(),
+        ))
+    })
+}
+
+/// A header as JSON, with no confirmations if its block is yet to be validated.
+fn header_json(header: &Header, height: u32, tip: u32) -> Value {
+    let confirmations = if height <= tip { tip - height + 1 } else { 0 };
+
+    json!({
+        "hash": header.block_hash().to_string(),
+        "confirmations": confirmations,
+        "height": height,
+        "version": header.version.to_consensus(),
+        "merkleroot": header.merkle_root.to_string(),
+        "time": header.time,
+        "nonce": header.nonce,
+        "bits": format!("{:08x}", header.bits.to_consensus()),
+        "difficulty": header.difficulty_float(),
+        "previousblockhash": header.prev_blockhash.to_string(),
+    })
+}
+
+async fn get_blockhash_by_height(
+    State(state): State<RestState>,
+    Path(file): Path<String>,
+) -> Result<Response, RestError> {
+    let (height, format) = split_format(&file)?;
+    let height: u32 = height
+        .parse()
+        .map_err(|_| RestError::bad_request(format!("Invalid height: {height}")))?;
+
+    let handle = state.node.upgrade()?;
+    let node = handle.read().await;
+    let tip = node.get_validation_height().map_err(RestError::internal)?;
+    if height > tip {
+        return Err(RestError::not_found("Block height out of range"));
+    }
+    let blockhash = node
+        .get_blockhash(height)
+        .map_err(|_| RestError::not_found("Block height out of range"))?;
+
+    respond(format, serialize(&blockhash), || {
+        Ok(json!({ "blockhash": blockhash.to_string() }))
+    })
+}
+
+async fn get_chain_info(State(state): State<RestState>) -> Result<Response, RestError> {
+    let handle = state.node.upgrade()?;
+    let node = handle.read().await;
+    let info = get_blockchain_info(&node, state.network).map_err(RestError::internal)?;
+
+    Ok(Json(info).into_response())
+}
@@ -0,0 +1,546 @@
+use core::fmt::Display;
+use std::fs;
+use std::io::Write;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::post;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bdk_floresta::ConnectionKind;
+use bdk_floresta::Node;
+use bdk_floresta::TransportProtocol;
+use bitcoin::Block;
+use bitcoin::BlockHash;
+use bitcoin::Network;
+use bitcoin::Transaction;
+use bitcoin::block::Header;
+use bitcoin::consensus::encode::deserialize_hex;
+use bitcoin::consensus::encode::serialize_hex;
+use chacha20poly1305::aead::OsRng;
+use chacha20poly1305::aead::rand_core::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+use crate::node::error::BonsaiNodeError;
+use crate::node::shutdown::NodeNotRunning;
+use crate::node::shutdown::WeakNode;
+
+/// Whether the JSON-RPC server is enabled by default.
+pub const RPC_SERVER: bool = false;
+
+/// The cookie file, written to the network's data directory while the server runs.
+pub const RPC_COOKIE_FILE: &str = ".cookie";
+
+/// The username of cookie authentication, as in Bitcoin Core.
+const COOKIE_USER: &str = "__cookie__";
+
+// Error codes, as in Bitcoin Core.
+pub const RPC_MISC_ERROR: i32 = -1;
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+pub const RPC_INVALID_PARAMETER: i32 = -8;
+const RPC_DESERIALIZATION_ERROR: i32 = -22;
+const RPC_VERIFY_ERROR: i32 = -25;
+const RPC_IN_WARMUP: i32 = -28;
+const RPC_CLIENT_NODE_NOT_CONNECTED: i32 = -29;
+pub const RPC_INVALID_REQUEST: i32 = -32600;
+pub const RPC_METHOD_NOT_FOUND: i32 = -32601;
+pub const RPC_PARSE_ERROR: i32 = -32700;
+
+/// The default JSON-RPC port of a [`Network`], as in Bitcoin Core.
+pub fn default_rpc_port(network: Network) -> u16 {
+    match network {
+        Network::Bitcoin => 8332,
+        Network::Testnet => 18332,
+        Network::Testnet4 => 48332,
+        Network::Regtest => 18443,
+        _ => 38332,
+    }
+}
+
+/// A JSON-RPC error, serialized as `{"code": .., "message": ..}`.
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn misc(e: impl Display) -> Self {
+        RpcError::new(RPC_MISC_ERROR, e.to_string())
+    }
+}
+
+impl From<NodeNotRunning> for RpcError {
+    fn from(e: NodeNotRunning) -> Self {
+        RpcError::new(RPC_IN_WARMUP, e.to_string())
+    }
+}
+
+impl Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    id: Value,
+}
+
+#[derive(Clone)]
+struct RpcState {
+    node: WeakNode,
+    network: Network,
+    /// The expected `Authorization` header.
+    authorization: Arc<str>,
+}
+
+/// A random cookie, written to disk for clients and removed when dropped.
+struct Cookie {
+    path: PathBuf,
+    authorization: String,
+}
+
+impl Cookie {
+    fn create(path: PathBuf) -> std::io::Result<Self> {
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        let cookie = format!("{COOKIE_USER}:{}", hex::encode(secret));
+
+        // Recreate the file, so it gets owner-only permissions.
+        let _ = fs::remove_file(&path);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(&path)?.write_all(cookie.as_bytes())?;
+
+        Ok(Cookie {
+            path,
+            authorization: format!("Basic {}", BASE64.encode(cookie)),
+        })
+    }
+}
+
+impl Drop for Cookie {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!("Failed to remove {}: {e}", self.path.to_string_lossy());
+        }
+    }
+}
+
+/// Serve JSON-RPC on localhost, with cookie authentication.
+///
+/// Implements a Bitcoin Core-compatible subset of methods, backed by the
+/// given node. Runs until dropped, which also removes the cookie file.
+pub async fn serve(
+    node: WeakNode,
+    network: Network,
+    port: u16,
+    datadir: PathBuf,
+) -> Result<(), BonsaiNodeError> {
+    let cookie_path = datadir.join(RPC_COOKIE_FILE);
+    let cookie = Cookie::create(cookie_path.clone()).map_err(|e| {
+        BonsaiNodeError::Generic(format!(
+            "Failed to write RPC cookie to {}: {e}",
+            cookie_path.to_string_lossy()
+        ))
+    })?;
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .await
+        .map_err(|e| {
+            BonsaiNodeError::Generic(format!("Failed to bind RPC server to port {port}: {e}"))
+        })?;
+    info!(
+        "JSON-RPC server listening on {}",
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port))
+    );
+
+    let state = RpcState {
+        node,
+        network,
+        authorization: Arc::from(cookie.authorization.as_str()),
+    };
+    let app = Router::new()
+        .route("/", post(handle_request))
+        .with_state(state);
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| BonsaiNodeError::Generic(format!("RPC server stopped: {e}")))
+}
+
+async fn handle_request(
+    State(state): State<RpcState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .is_some_and(|value| value.as_bytes() == state.authorization.as_bytes());
+    if !authorized {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Basic realm=\"jsonrpc\"")],
+        )
+            .into_response();
+    }
+
+    let response = match serde_json::from_str::<Value>(&body) {
+        Ok(Value::Array(calls)) => {
+            let mut responses = Vec::with_capacity(calls.len());
+            for call in calls {
+                responses.push(handle_call(&state, call).await);
+            }
+            Value::Array(responses)
+        }
+        Ok(call) => handle_call(&state, call).await,
+        Err(e) => rpc_response(
+            Value::Null,
+            Err(RpcError::new(RPC_PARSE_ERROR, format!("Parse error: {e}"))),
+        ),
+    };
+
+    axum::Json(response).into_response()
+}
+
+async fn handle_call(state: &RpcState, call: Value) -> Value {
+    let request: RpcRequest = match serde_json::from_value(call) {
+        Ok(request) => request,
+        Err(e) => {
+            return rpc_response(
+                Value::Null,
+                Err(RpcError::new(RPC_INVALID_REQUEST, e.to_string())),
+            );
+        }
+    };
+
+    debug!("RPC call to {}", request.method);
+    let result = dispatch(state, &request.method, &request.params).await;
+    rpc_response(request.id, result)
+}
+
+fn rpc_response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "result": result, "error": null, "id": id }),
+        Err(error) => json!({ "result": null, "error": error, "id": id }),
+    }
+}
+
+async fn dispatch(state: &RpcState, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let handle = state.node.upgrade()?;
+    let node = handle.read().await;
+
+    match method {
+        "getblockchaininfo" => get_blockchain_info(&node, state.network),
+        "getblockcount" => Ok(json!(node.get_validation_height().map_err(RpcError::misc)?)),
+        "getblockhash" => get_block_hash(&node, params),
+        "getblock" => get_block(&node, params).await,
+        "getpeerinfo" => get_peer_info(&node).await,
+        "addnode" => add_node(&node, params).await,
+        "disconnectnode" => disconnect_node(&node, params).await,
+        "sendrawtransaction" => send_raw_transaction(&node, params).await,
+        _ => Err(RpcError::new(RPC_METHOD_NOT_FOUND, "Method not found")),
+    }
+}
+
+/// An optional parameter, given by position or by name.
+pub fn param<T: DeserializeOwned>(
+    params: &Value,
+    index: usize,
+    name: &str,
+) -> Result<Option<T>, RpcError> {
+    let value = match params {
+        Value::Array(values) => values.get(index),
+        Value::Object(values) => values.get(name),
+        _ => None,
+    };
+
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => T::deserialize(value)
+            .map(Some)
+            .map_err(|e| RpcError::new(RPC_INVALID_PARAMETER, format!("Invalid {name}: {e}"))),
+    }
+}
+
+/// A required parameter, given by position or by name.
+pub fn required<T: DeserializeOwned>(
+    params: &Value,
+    index: usize,
+    name: &str,
+) -> Result<T, RpcError> {
+    param(params, index, name)?
+        .ok_or_else(|| RpcError::new(RPC_INVALID_PARAMETER, format!("Missing {name}")))
+}
+
+pub fn get_blockchain_info(node: &Node, network: Network) -> Result<Value, RpcError> {
+    let blocks = node.get_validation_height().map_err(RpcError::misc)?;
+    let headers = node.get_height().map_err(RpcError::misc)?;
+    let best_block_hash = node.get_blockhash(blocks).map_err(RpcError::misc)?;
+    let in_ibd = node.in_ibd().map_err(RpcError::misc)?;
+    let verification_progress = if headers == 0 {
+        0.0
+    } else {
+        blocks as f64 / headers as f64
+    };
+
+    Ok(json!({
+        "chain": network.to_core_arg(),
+        "blocks": blocks,
+        "headers": headers,
+        "bestblockhash": best_block_hash.to_string(),
+        "verificationprogress": verification_progress,
+        "initialblockdownload": in_ibd,
+        // Blocks are not stored, only the accumulator.
+        "pruned": true,
+        "warnings": "",
+    }))
+}
+
+/// The header at `height` in the node's header chain, which runs ahead of validation.
+pub fn get_header(node: &Node, height: u32) -> Result<Header, RpcError> {
+    let blockhash = node
+        .get_blockhash(height)
+        .map_err(|_| RpcError::new(RPC_INVALID_PARAMETER, "Block height out of range"))?;
+
+    node.get_block_header(blockhash)
+        .map_err(|e| RpcError::misc(format!("Header of block {blockhash} not available: {e}")))
+}
+
+fn get_block_hash(node: &Node, params: &Value) -> Result<Value, RpcError> {
+    let height: u32 = required(params, 0, "height")?;
+    let tip = node.get_validation_height().map_err(RpcError::misc)?;
+    if height > tip {
+        return Err(RpcError::new(
+            RPC_INVALID_PARAMETER,
+            "Block height out of range",
+        ));
+    }
+
+    let blockhash = node
+        .get_blockhash(height)
+        .map_err(|_| RpcError::new(RPC_INVALID_PARAMETER, "Block height out of range"))?;
+
+    Ok(json!(blockhash.to_string()))
+}
+
+async fn get_block(node: &Node, params: &Value) -> Result<Value, RpcError> {
+    let blockhash: String = required(params, 0, "blockhash")?;
+    let blockhash: BlockHash = blockhash.parse().map_err(|_| {
+        RpcError::new(
+            RPC_INVALID_PARAMETER,
+            "blockhash must be of length 64 (not a hex string)",
+        )
+    })?;
+
+    // Bitcoin Core accepts a boolean verbose flag, or a verbosity level.
+    let verbosity = match param::<Value>(params, 1, "verbosity")? {
+        None => 1,
+        Some(Value::Bool(verbose)) => u64::from(verbose),
+        Some(Value::Number(verbosity)) => verbosity.as_u64().unwrap_or(1),
+        Some(_) => {
+            return Err(RpcError::new(
+                RPC_INVALID_PARAMETER,
+                "verbosity must be a number",
+            ));
+        }
+    };
+
+    let block = node
+        .get_block(blockhash)
+        .await
+        .map_err(RpcError::misc)?
+        .ok_or_else(|| RpcError::new(RPC_INVALID_ADDRESS_OR_KEY, "Block not found"))?;
+
+    if verbosity == 0 {
+        return Ok(json!(serialize_hex(&block)));
+    }
+
+    let tip = node.get_validation_height().map_err(RpcError::misc)?;
+    let height = node.get_block_height(blockhash).ok().flatten();
+    Ok(block_json(&block, height, verbosity, tip))
+}
+
+/// A block as JSON, with its `height` looked up by hash, since blocks before BIP34 don't carry it.
+pub fn block_json(block: &Block, height: Option<u32>, verbosity: u64, tip: u32) -> Value {
+    let txs: Vec<Value> = if verbosity >= 2 {
+        block.txdata.iter().map(tx_json).collect()
+    } else {
+        block
+            .txdata
+            .iter()
+            .map(|tx| json!(tx.compute_txid().to_string()))
+            .collect()
+    };
+
+    json!({
+        "hash": block.block_hash().to_string(),
+        "confirmations": height.map(|height| i64::from(tip) - i64::from(height) + 1),
+        "height": height,
+        "size": block.total_size(),
+        "strippedsize": block.base_size(),
+        "weight": block.weight().to_wu(),
+        "version": block.header.version.to_consensus(),
+        "merkleroot": block.header.merkle_root.to_string(),
+        "tx": txs,
+        "time": block.header.time,
+        "nonce": block.header.nonce,
+        "bits": format!("{:08x}", block.header.bits.to_consensus()),
+        "difficulty": block.header.difficulty_float(),
+        "nTx": block.txdata.len(),
+        "previousblockhash": block.header.prev_blockhash.to_string(),
+    })
+}
+
+fn tx_json(tx: &Transaction) -> Value {
+    json!({
+        "txid": tx.compute_txid().to_string(),
+        "hash": tx.compute_wtxid().to_string(),
+        "version": tx.version.0,
+        "size": tx.total_size(),
+        "vsize": tx.vsize(),
+        "weight": tx.weight().to_wu(),
+        "locktime": tx.lock_time.to_consensus_u32(),
+        "hex": serialize_hex(tx),
+    })
+}
+
+async fn get_peer_info(node: &Node) -> Result<Value, RpcError> {
+    let peer_infos = node.get_peer_info().await.map_err(RpcError::misc)?;
+
+    let peers: Vec<Value> = peer_infos
+        .iter()
+        .enumerate()
+        .map(|(id, peer)| {
+            json!({
+                "id": id,
+                "addr": peer.address.to_string(),
+                "services": format!("{:016x}", peer.services.to_u64()),
+                "subver": peer.user_agent,
+                "startingheight": peer.initial_height,
+                // The node only makes outbound connections.
+                "inbound": false,
+                "connection_type": match peer.kind {
+                    ConnectionKind::Manual => "manual",
+                    ConnectionKind::Feeler => "feeler",
+                    _ => "outbound-full-relay",
+                },
+                "transport_protocol_type": match peer.transport_protocol {
+                    TransportProtocol::V1 => "v1",
+                    TransportProtocol::V2 => "v2",
+                },
+            })
+        })
+        .collect();
+
+    Ok(Value::Array(peers))
+}
+
+/// Resolve a `host:port` node address.
+async fn resolve_node(address: &str) -> Result<SocketAddr, RpcError> {
+    tokio::net::lookup_host(address)
+        .await
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| {
+            RpcError::new(
+                RPC_INVALID_PARAMETER,
+                format!("Invalid node address {address}, expected host:port"),
+            )
+        })
+}
+
+async fn add_node(node: &Node, params: &Value) -> Result<Value, RpcError> {
+    let address: String = required(params, 0, "node")?;
+    let command: String = required(params, 1, "command")?;
+
+    match command.as_str() {
+        "add" | "onetry" => {
+            let socket = resolve_node(&address).await?;
+            match node.connect_peer(&socket).await {
+                Ok(true) => Ok(Value::Null),
+                Ok(false) => Err(RpcError::new(
+                    RPC_CLIENT_NODE_NOT_CONNECTED,
+                    "Failed to connect to peer",
+                )),
+                Err(e) => Err(RpcError::misc(e)),
+            }
+        }
+        "remove" => {
+            let socket = resolve_node(&address).await?;
+            node.disconnect_peer(&socket)
+                .await
+                .map(|_| Value::Null)
+                .map_err(|e| RpcError::new(RPC_CLIENT_NODE_NOT_CONNECTED, e.to_string()))
+        }
+        _ => Err(RpcError::new(
+            RPC_INVALID_PARAMETER,
+            "command must be one of add, remove or onetry",
+        )),
+    }
+}
+
+async fn disconnect_node(node: &Node, params: &Value) -> Result<Value, RpcError> {
+    let address: String = required(params, 0, "address")?;
+    let socket: SocketAddr = address.parse().map_err(|_| {
+        RpcError::new(
+            RPC_INVALID_PARAMETER,
+            format!("Invalid address {address}, expected ip:port"),
+        )
+    })?;
+
+    node.disconnect_peer(&socket)
+        .await
+        .map(|_| Value::Null)
+        .map_err(|_| {
+            RpcError::new(
+                RPC_CLIENT_NODE_NOT_CONNECTED,
+                "Node not found in connected nodes",
+            )
+        })
+}
+
+async fn send_raw_transaction(node: &Node, params: &Value) -> Result<Value, RpcError> {
+    let hex: String = required(params, 0, "hexstring")?;
+    let tx: Transaction = deserialize_hex(&hex)
+        .map_err(|e| RpcError::new(RPC_DESERIALIZATION_ERROR, format!("TX decode failed: {e}")))?;
+    let txid = tx.compute_txid();
+
+    node.broadcast_transaction(tx)
+        .await
+        .map_err(|e| RpcError::new(RPC_VERIFY_ERROR, e.to_string()))?;
+    info!("Broadcast transaction {txid} received over RPC");
+
+    Ok(json!(txid.to_string()))
+}
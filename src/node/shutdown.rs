@@ -0,0 +1,179 @@
+use core::fmt::Display;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+use bdk_floresta::Node;
+use iced::futures::SinkExt;
+use iced::futures::Stream;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tokio::sync::watch;
+use tracing::info;
+use tracing::warn;
+
+use crate::node::control::stop_node;
+use crate::node::error::BonsaiNodeError;
+use crate::node::message::NodeMessage;
+
+/// How long to wait for in-flight tasks to drop their node handles.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often to check whether in-flight tasks dropped their node handles.
+const HANDLE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lets a task that holds a node handle be cancelled by a [`TaskCanceller`].
+#[derive(Clone)]
+pub struct CancellationToken(watch::Receiver<bool>);
+
+impl CancellationToken {
+    /// Run a future to completion, or drop it if cancelled first.
+    pub async fn run<F: Future>(mut self, future: F) -> Option<F::Output> {
+        tokio::select! {
+            output = future => Some(output),
+            _ = self.0.wait_for(|cancelled| *cancelled) => None,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.0.borrow()
+    }
+}
+
+/// Hands out [`CancellationToken`]s to tasks that hold a node handle,
+/// and cancels all of them at once on shutdown.
+///
+/// Dropping the [`TaskCanceller`] also cancels its tokens.
+pub struct TaskCanceller(watch::Sender<bool>);
+
+impl Default for TaskCanceller {
+    fn default() -> Self {
+        TaskCanceller(watch::channel(false).0)
+    }
+}
+
+impl TaskCanceller {
+    pub fn token(&self) -> CancellationToken {
+        CancellationToken(self.0.subscribe())
+    }
+
+    pub fn cancel(&self) {
+        self.0.send_replace(true);
+    }
+}
+
+/// A node handle for servers, which only reaches the node while it runs.
+///
+/// Servers are cancelled on shutdown, but a request in flight may still be holding
+/// the node. Holding it weakly between requests means a server never keeps a stopped
+/// node alive, and a shutdown only waits for the requests in flight. Once the shutdown
+/// started, new requests are refused, even on connections the server left open.
+#[derive(Clone)]
+pub struct WeakNode(Weak<RwLock<Node>>, CancellationToken);
+
+/// The error of reaching the node through a [`WeakNode`] once it stopped.
+#[derive(Debug, Error)]
+#[error("Node is not running")]
+pub struct NodeNotRunning;
+
+impl WeakNode {
+    pub fn new(handle: &Arc<RwLock<Node>>, token: CancellationToken) -> Self {
+        WeakNode(Arc::downgrade(handle), token)
+    }
+
+    /// The node, for the duration of a request.
+    pub fn upgrade(&self) -> Result<Arc<RwLock<Node>>, NodeNotRunning> {
+        let node = self.0.upgrade().ok_or(NodeNotRunning)?;
+        // Checked after upgrading, so either the shutdown counts this handle,
+        // or this handle sees the shutdown and is dropped right away.
+        if self.1.is_cancelled() {
+            return Err(NodeNotRunning);
+        }
+        Ok(node)
+    }
+}
+
+/// The current step of a node shutdown.
+#[derive(Clone, Debug)]
+pub enum ShutdownProgress {
+    CancellingTasks,
+    /// Waiting for this many tasks to drop their node handles.
+    WaitingForTasks(usize),
+    StoppingNode,
+}
+
+impl Display for ShutdownProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CancellingTasks => write!(f, "CANCELLING TASKS"),
+            Self::WaitingForTasks(count) => write!(f, "WAITING FOR {count} TASKS"),
+            Self::StoppingNode => write!(f, "STOPPING NODE"),
+        }
+    }
+}
+
+/// Shut the node down gracefully: cancel in-flight tasks, wait up to
+/// [`SHUTDOWN_TIMEOUT`] for them to drop their node handles, then stop the node.
+///
+/// Emits [`NodeMessage::ShutdownProgress`] along the way, and finishes
+/// with [`NodeMessage::ShutdownFinished`].
+pub fn shutdown(
+    node_handle: Arc<RwLock<Node>>,
+    canceller: TaskCanceller,
+) -> impl Stream<Item = NodeMessage> {
+    iced::stream::channel(
+        10,
+        move |mut output: iced::futures::channel::mpsc::Sender<NodeMessage>| async move {
+            let _ = output
+                .send(NodeMessage::ShutdownProgress(
+                    ShutdownProgress::CancellingTasks,
+                ))
+                .await;
+            canceller.cancel();
+
+            let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+            let mut node_handle = node_handle;
+            let mut last_remaining = 0;
+            let node = loop {
+                // Unwrapping, rather than counting handles, leaves no gap for a
+                // handle to be taken between the last check and the shutdown.
+                match Arc::try_unwrap(node_handle) {
+                    Ok(node) => break node,
+                    Err(handle) => node_handle = handle,
+                }
+                let remaining = Arc::strong_count(&node_handle) - 1;
+
+                if Instant::now() >= deadline {
+                    warn!("Timed out waiting for {remaining} tasks to drop their node handles");
+                    let _ = output
+                        .send(NodeMessage::ShutdownFinished(Err(
+                            BonsaiNodeError::ShutdownTimeout(remaining),
+                        )))
+                        .await;
+                    return;
+                }
+
+                if remaining != last_remaining {
+                    info!("Waiting for {remaining} tasks to drop their node handles");
+                    let _ = output
+                        .send(NodeMessage::ShutdownProgress(
+                            ShutdownProgress::WaitingForTasks(remaining),
+                        ))
+                        .await;
+                    last_remaining = remaining;
+                }
+
+                tokio::time::sleep(HANDLE_POLL_INTERVAL).await;
+            };
+
+            let _ = output
+                .send(NodeMessage::ShutdownProgress(
+                    ShutdownProgress::StoppingNode,
+                ))
+                .await;
+            let result = stop_node(node).await.map_err(BonsaiNodeError::from);
+            let _ = output.send(NodeMessage::ShutdownFinished(result)).await;
+        },
+    )
+}
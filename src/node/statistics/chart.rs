@@ -0,0 +1,99 @@
+use iced::Color;
+use iced::Point;
+use iced::Rectangle;
+use iced::Renderer;
+use iced::Theme;
+use iced::mouse;
+use iced::widget::canvas;
+use iced::widget::canvas::Frame;
+use iced::widget::canvas::Geometry;
+use iced::widget::canvas::Path;
+use iced::widget::canvas::Stroke;
+
+use crate::common::interface::color::GREEN_SHAMROCK;
+use crate::common::interface::color::OFF_WHITE;
+use crate::common::interface::color::ORANGE;
+use crate::node::sync_history::SyncHistory;
+use crate::node::sync_history::SyncSample;
+
+/// The color of the validated height in the [`SyncChart`].
+pub const HEIGHT_COLOR: Color = GREEN_SHAMROCK;
+
+/// The color of the header gap in the [`SyncChart`].
+pub const GAP_COLOR: Color = ORANGE;
+
+/// Padding around the plot, so strokes at the edges aren't cut.
+const PLOT_PADDING: f32 = 4.0;
+
+/// Plots the validated height and the header gap of a [`SyncHistory`] over time.
+///
+/// Both series share the time axis, but each is scaled to its own maximum.
+pub struct SyncChart<'a> {
+    pub history: &'a SyncHistory,
+}
+
+impl<Message> canvas::Program<Message> for SyncChart<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let width = bounds.width - 2.0 * PLOT_PADDING;
+        let height = bounds.height - 2.0 * PLOT_PADDING;
+
+        let baseline = Path::line(
+            Point::new(PLOT_PADDING, PLOT_PADDING + height),
+            Point::new(PLOT_PADDING + width, PLOT_PADDING + height),
+        );
+        frame.stroke(
+            &baseline,
+            Stroke::default().with_color(OFF_WHITE.scale_alpha(0.3)),
+        );
+
+        let samples = &self.history.samples;
+        let (Some(first), Some(last)) = (samples.front(), samples.back()) else {
+            return vec![frame.into_geometry()];
+        };
+        let span = (last.time - first.time).max(1) as f32;
+        let max_headers = samples.iter().map(|s| s.headers).max().unwrap_or(0).max(1) as f32;
+        let max_gap = samples
+            .iter()
+            .map(SyncSample::gap)
+            .max()
+            .unwrap_or(0)
+            .max(1) as f32;
+
+        let plot = |value: fn(&SyncSample) -> u32, max: f32| {
+            Path::new(|builder| {
+                for (i, sample) in samples.iter().enumerate() {
+                    let point = Point::new(
+                        PLOT_PADDING + (sample.time - first.time) as f32 / span * width,
+                        PLOT_PADDING + height - value(sample) as f32 / max * height,
+                    );
+                    if i == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            })
+        };
+
+        frame.stroke(
+            &plot(SyncSample::gap, max_gap),
+            Stroke::default().with_color(GAP_COLOR).with_width(2.0),
+        );
+        frame.stroke(
+            &plot(|sample: &SyncSample| sample.blocks, max_headers),
+            Stroke::default().with_color(HEIGHT_COLOR).with_width(2.0),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
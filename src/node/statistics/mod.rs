@@ -1,2 +1,3 @@
-pub(crate) mod style;
-pub(crate) mod view;
+pub mod chart;
+pub mod style;
+pub mod view;
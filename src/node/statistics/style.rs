@@ -1,5 +1,6 @@
 use iced::Background::Color as BackgroundColor;
 use iced::Border;
+use iced::Color;
 use iced::Theme;
 use iced::border::Radius;
 use iced::theme::palette::Pair;
@@ -18,7 +19,7 @@ use crate::common::interface::shadow::SHADOW_GRAY;
 use crate::node::control::NodeStatus;
 
 /// Container for displaying logs through a `tracing_subscriber`.
-pub(crate) fn log_container() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn log_container() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
@@ -30,15 +31,53 @@ pub(crate) fn log_container() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
+/// Toggle buttons of the log panel, filled with `color` while `active`.
+pub fn log_toggle_button(
+    active: bool,
+    color: Color,
+) -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
+    move |_theme, button_status| {
+        let (background, text_color) = if active {
+            (Some(BackgroundColor(color)), BLACK)
+        } else {
+            (None, color)
+        };
+        let text_color = match button_status {
+            ButtonStatus::Hovered | ButtonStatus::Pressed => text_color.scale_alpha(0.7),
+            ButtonStatus::Disabled => text_color.scale_alpha(0.5),
+            ButtonStatus::Active => text_color,
+        };
+
+        ButtonStyle {
+            background,
+            text_color,
+            border: Border {
+                color,
+                width: BORDER_WIDTH,
+                radius: Radius::new(BORDER_RADIUS),
+            },
+            ..ButtonStyle::default()
+        }
+    }
+}
+
+/// Highlight of log lines selected for copying.
+pub fn selected_log() -> impl Fn(&Theme) -> ContainerStyle {
+    |_theme| ContainerStyle {
+        background: Some(BackgroundColor(OFF_WHITE.scale_alpha(0.15))),
+        ..Default::default()
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
-pub(crate) enum ControlButton {
+pub enum ControlButton {
     Start,
     Restart,
     Shutdown,
 }
 
 /// Style for `ACTION` buttons on the `NODE OVERVIEW` tab.
-pub(crate) fn action_button(
+pub fn action_button(
     node_status: &NodeStatus,
     action_button: ControlButton,
 ) -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
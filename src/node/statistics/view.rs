@@ -1,5 +1,8 @@
+use std::time::Instant;
+
 use bitcoin::Network;
 use iced::Alignment::Center;
+use iced::Color;
 use iced::Element;
 use iced::Length;
 use iced::Length::Fill;
@@ -7,12 +10,16 @@ use iced::Padding;
 use iced::widget::Container;
 use iced::widget::Space;
 use iced::widget::button;
+use iced::widget::canvas;
 use iced::widget::column;
 use iced::widget::container;
+use iced::widget::mouse_area;
 use iced::widget::row;
 use iced::widget::scrollable;
 use iced::widget::text;
 use iced::widget::text::Wrapping;
+use iced::widget::text_input;
+use tracing::Level;
 
 use crate::common::interface::color::BLUE;
 use crate::common::interface::color::GREEN_SHAMROCK;
@@ -22,18 +29,48 @@ use crate::common::interface::color::RED;
 use crate::common::interface::color::network_color;
 use crate::common::interface::container::button_container;
 use crate::common::util::format_duration;
+use crate::common::util::format_mebibytes;
 use crate::common::util::format_thousands;
 use crate::node::control::NodeStatus;
+use crate::node::control::RestartPolicy;
+use crate::node::control::Supervisor;
 use crate::node::log_capture::LogCapture;
+use crate::node::log_capture::LogTarget;
+use crate::node::log_capture::LogView;
 use crate::node::message::NodeMessage;
+use crate::node::proxy::ProxyStatus;
+use crate::node::shutdown::ShutdownProgress;
+use crate::node::statistics::chart::GAP_COLOR;
+use crate::node::statistics::chart::HEIGHT_COLOR;
+use crate::node::statistics::chart::SyncChart;
 use crate::node::statistics::style::ControlButton;
 use crate::node::statistics::style::action_button;
 use crate::node::statistics::style::log_container;
+use crate::node::statistics::style::log_toggle_button;
+use crate::node::statistics::style::selected_log;
 use crate::node::stats_fetcher::NodeStatistics;
 use crate::node::style::TITLE_PADDING;
+use crate::node::style::input_field;
 use crate::node::style::table_cell;
 use crate::node::style::title_container;
-use crate::pulse_color;
+use crate::node::sync_history::SyncHistory;
+
+/// The height of the sync chart.
+const SYNC_CHART_HEIGHT: f32 = 100.0;
+
+/// The height of a log line, so the rows on screen can be told from the scroll offset.
+const LOG_LINE_HEIGHT: f32 = 18.0;
+
+/// The color of log lines at `level`.
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::ERROR => RED,
+        Level::WARN => ORANGE,
+        Level::INFO => GREEN_SHAMROCK,
+        Level::DEBUG => BLUE,
+        _ => OFF_WHITE,
+    }
+}
 
 /// Calculate IBD progress from blocks and headers.
 fn calculate_progress(blocks: u32, headers: u32) -> f64 {
@@ -72,11 +109,16 @@ fn control_button_with_disable_logic<'a>(
     }
 }
 
-pub(crate) fn view_statistics<'a>(
+pub fn view_statistics<'a>(
     network: Network,
     node_status: &'a NodeStatus,
     statistics: &'a Option<NodeStatistics>,
     log_capture: &'a LogCapture,
+    log_view: &'a LogView,
+    shutdown_progress: &'a Option<ShutdownProgress>,
+    supervisor: &'a Supervisor,
+    sync_history: &'a SyncHistory,
+    proxy_status: &'a ProxyStatus,
     app_clock: usize,
 ) -> Element<'a, NodeMessage> {
     // Control Button Section.
@@ -106,7 +148,65 @@ pub(crate) fn view_statistics<'a>(
     )
     .style(title_container())
     .padding(10);
-    let control = column![control_button_title, control_button_container];
+    let mut control = column![control_button_title, control_button_container];
+
+    // Failure Section.
+    if let NodeStatus::Failed(e) = node_status {
+        let retry_status = match (supervisor.retry_at, supervisor.policy) {
+            (Some(retry_at), _) => format!(
+                "RETRYING IN {}S [ATTEMPT {}/{}]",
+                retry_at.saturating_duration_since(Instant::now()).as_secs(),
+                supervisor.attempts,
+                supervisor.max_attempts
+            ),
+            (None, RestartPolicy::Never) => "AUTOMATIC RESTARTS DISABLED".to_string(),
+            (None, _) if supervisor.attempts > supervisor.max_attempts => {
+                "AUTOMATIC RETRIES EXHAUSTED".to_string()
+            }
+            (None, _) => "NOT RESTARTED BY THE RESTART POLICY".to_string(),
+        };
+
+        control = control.push(
+            container(
+                column![
+                    text(e.explanation().to_uppercase()).size(14).color(RED),
+                    text(e.to_string()).size(12).wrapping(Wrapping::Glyph),
+                    row![
+                        text(retry_status).size(12).width(Fill),
+                        button(text("RETRY NOW").size(12))
+                            .on_press(NodeMessage::Start)
+                            .style(button_container())
+                            .padding(5),
+                    ]
+                    .spacing(10)
+                    .align_y(Center),
+                ]
+                .spacing(10),
+            )
+            .style(title_container())
+            .padding(10),
+        );
+    }
+
+    // Restarts Section.
+    if !supervisor.restarts.is_empty() {
+        let mut restarts = column![text("RESTARTS").size(14)].spacing(5);
+        // Only the most recent ones fit next to the metrics.
+        for restart in supervisor.restarts.iter().take(3) {
+            restarts = restarts.push(
+                text(format!(
+                    "{} [ATTEMPT {}] {}",
+                    restart.time.format("%Y-%m-%d %H:%M:%S"),
+                    restart.attempt,
+                    restart.reason
+                ))
+                .size(12)
+                .wrapping(Wrapping::Glyph),
+            );
+        }
+
+        control = control.push(container(restarts).style(title_container()).padding(10));
+    }
 
     // Metrics Section.
     let ibd_status = statistics.as_ref().map(|s| s.in_ibd).unwrap_or(true);
@@ -125,14 +225,47 @@ pub(crate) fn view_statistics<'a>(
         .as_ref()
         .map(|stats| format_duration(stats.uptime))
         .unwrap_or("00h 00m 00s".to_string());
+    let memory = statistics
+        .as_ref()
+        .and_then(|s| s.memory)
+        .map(format_mebibytes)
+        .unwrap_or("NULL".to_string());
+    let allocated_memory = statistics
+        .as_ref()
+        .and_then(|s| s.allocated_memory)
+        .map(format_mebibytes)
+        .unwrap_or("NULL".to_string());
+    // TODO: add backfill progress getter to NodeInterface, only whether it runs is known.
+    let backfill = match statistics {
+        Some(s) if s.backfill => "N/A",
+        Some(_) => "DISABLED",
+        None => "NULL",
+    };
+    let compact_filters = match statistics {
+        Some(s) if s.compact_filters => match s.filter_height {
+            Some(height) => format!(
+                "{}\n[{:.2}%]",
+                format_thousands(height),
+                calculate_progress(height, headers)
+            ),
+            None => "N/A".to_string(),
+        },
+        Some(_) => "DISABLED".to_string(),
+        None => "NULL".to_string(),
+    };
+    // The SOCKS5 proxy, such as Tor, the node's connections go through.
+    let proxy_color = match proxy_status {
+        ProxyStatus::Disabled => OFF_WHITE,
+        ProxyStatus::Checking(_) => ORANGE,
+        ProxyStatus::Reachable(_) => GREEN_SHAMROCK,
+        ProxyStatus::Unreachable(..) => RED,
+    };
 
     let network_color = network_color(&network);
-    let node_status_color = match node_status {
-        NodeStatus::Starting => pulse_color(GREEN_SHAMROCK, app_clock),
-        NodeStatus::Running => GREEN_SHAMROCK,
-        NodeStatus::Inactive => OFF_WHITE,
-        NodeStatus::Failed(_) => RED,
-        NodeStatus::ShuttingDown => pulse_color(RED, app_clock),
+    let node_status_color = node_status.color(app_clock);
+    let node_status_text = match (node_status, shutdown_progress) {
+        (NodeStatus::ShuttingDown, Some(progress)) => format!("{node_status} [{progress}]"),
+        _ => node_status.to_string(),
     };
 
     let metrics_title = container(text("NODE STATISTICS").size(24));
@@ -143,14 +276,10 @@ pub(crate) fn view_statistics<'a>(
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
-                container(
-                    text(node_status.to_string())
-                        .size(14)
-                        .color(node_status_color)
-                )
-                .padding(10)
-                .width(Length::FillPortion(1))
-                .style(table_cell()),
+                container(text(node_status_text).size(14).color(node_status_color))
+                    .padding(10)
+                    .width(Length::FillPortion(1))
+                    .style(table_cell()),
             ],
             row![
                 container(text("NETWORK").size(14))
@@ -171,10 +300,15 @@ pub(crate) fn view_statistics<'a>(
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
-                container(text("TODO").size(14))
-                    .padding(10)
-                    .width(Length::FillPortion(1))
-                    .style(table_cell()),
+                container(
+                    text(proxy_status.to_string())
+                        .size(14)
+                        .color(proxy_color)
+                        .wrapping(Wrapping::None)
+                )
+                .padding(10)
+                .width(Length::FillPortion(1))
+                .style(table_cell()),
             ],
             row![
                 container(text("IBD STATUS").size(14))
@@ -201,7 +335,7 @@ pub(crate) fn view_statistics<'a>(
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
-                container(text("TODO").size(14)) // TODO: add backfill status getter to NodeInterface
+                container(text(backfill).size(14))
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
@@ -227,13 +361,13 @@ pub(crate) fn view_statistics<'a>(
                     .style(table_cell()),
             ],
             row![
-                container(text("COMPACT\nBLOCK FILTERS").size(14)) // TODO: add CBF info getter to NodeInterface
+                container(text("COMPACT\nBLOCK FILTERS").size(14))
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .height(Length::Fixed(60.0))
                     .align_y(Center)
                     .style(table_cell()),
-                container(text("TODO").size(14))
+                container(text(compact_filters).size(14))
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .height(Length::Fixed(60.0))
@@ -279,7 +413,7 @@ pub(crate) fn view_statistics<'a>(
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
-                container(text("TODO").size(14).wrapping(text::Wrapping::None))
+                container(text(memory).size(14).wrapping(text::Wrapping::None))
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
@@ -289,7 +423,7 @@ pub(crate) fn view_statistics<'a>(
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
-                container(text("TODO").size(14))
+                container(text(allocated_memory).size(14))
                     .padding(10)
                     .width(Length::FillPortion(1))
                     .style(table_cell()),
@@ -304,11 +438,61 @@ pub(crate) fn view_statistics<'a>(
         .spacing(20)
         .width(Length::FillPortion(4));
 
+    // Sync Section.
+    let sync_rate = match (sync_history.rate(), sync_history.eta()) {
+        (Some(rate), Some(eta)) => format!("{rate:.1} BLOCKS/S [ETA {}]", format_duration(eta)),
+        (Some(rate), None) => format!("{rate:.1} BLOCKS/S"),
+        (None, _) if matches!(node_status, NodeStatus::Running) => "MEASURING...".to_string(),
+        (None, _) => String::new(),
+    };
+    let sync_title = container(
+        row![
+            text("SYNC").size(24),
+            Space::new().width(Length::Fill),
+            text(sync_rate).size(14),
+        ]
+        .spacing(10)
+        .align_y(Center),
+    )
+    .width(Length::Fill);
+    let sync_chart = container(
+        column![
+            row![
+                text("HEIGHT").size(12).color(HEIGHT_COLOR),
+                text("HEADER GAP").size(12).color(GAP_COLOR),
+            ]
+            .spacing(10),
+            canvas(SyncChart {
+                history: sync_history
+            })
+            .width(Length::Fill)
+            .height(SYNC_CHART_HEIGHT),
+        ]
+        .spacing(5),
+    )
+    .style(title_container())
+    .padding(10);
+
     // Logs Section.
+    let paused = log_view.paused_at.is_some();
     let log_title = container(
         row![
             text("LOGS").size(24),
             Space::new().width(Length::Fill),
+            button(text(if paused { "FOLLOW" } else { "PAUSE" }).size(14))
+                .on_press(NodeMessage::ToggleLogsPaused)
+                .style(button_container())
+                .padding(2),
+            button(text(format!("COPY [{}]", log_view.selected.len())).size(14))
+                .on_press_maybe(
+                    (!log_view.selected.is_empty()).then_some(NodeMessage::CopySelectedLogs)
+                )
+                .style(button_container())
+                .padding(2),
+            button(text("DIAGNOSTICS").size(14))
+                .on_press(NodeMessage::ExportDiagnostics)
+                .style(button_container())
+                .padding(2),
             button(text("CLEAR").size(14))
                 .on_press(NodeMessage::ClearLogs)
                 .style(button_container())
@@ -319,48 +503,107 @@ pub(crate) fn view_statistics<'a>(
     )
     .width(Length::Fill);
 
-    let mut log_column = column![].spacing(2).padding(Padding {
+    let mut level_toggles = row![].spacing(5);
+    for level in [
+        Level::ERROR,
+        Level::WARN,
+        Level::INFO,
+        Level::DEBUG,
+        Level::TRACE,
+    ] {
+        let shown = !log_view.filter.hidden_levels.contains(&level);
+        level_toggles = level_toggles.push(
+            button(text(level.as_str()).size(12))
+                .on_press(NodeMessage::LogLevelToggled(level))
+                .style(log_toggle_button(shown, level_color(level)))
+                .padding(2),
+        );
+    }
+
+    let mut target_toggles = row![].spacing(5);
+    for target in LogTarget::TARGETS {
+        target_toggles = target_toggles.push(
+            button(text(target.to_string()).size(12))
+                .on_press(NodeMessage::LogTargetChanged(target))
+                .style(log_toggle_button(
+                    log_view.filter.target == target,
+                    OFF_WHITE,
+                ))
+                .padding(2),
+        );
+    }
+
+    let log_filters = column![
+        row![
+            level_toggles,
+            Space::new().width(Length::Fill),
+            target_toggles
+        ]
+        .align_y(Center),
+        text_input("SEARCH", &log_view.filter.search)
+            .on_input(NodeMessage::LogSearchChanged)
+            .size(12)
+            .padding(5)
+            .style(input_field()),
+    ]
+    .spacing(5);
+
+    let mut log_column = column![].padding(Padding {
         top: 0.0,
         right: 10.0,
         bottom: 0.0,
         left: 0.0,
     });
 
-    let logs = log_capture.get_logs();
+    // Only the rows on screen are rendered, between spacers standing in for the rest.
+    let total = log_view.matching.len();
+    let end = total.saturating_sub((log_view.scroll_from_bottom / LOG_LINE_HEIGHT) as usize);
+    let start =
+        end.saturating_sub((log_view.viewport_height / LOG_LINE_HEIGHT).ceil() as usize + 1);
+    let rows = log_capture.get(log_view.matching.range(start..end).copied());
 
-    if logs.is_empty() {
-        log_column = log_column.push(text("").size(12));
-    } else {
-        // Keep the last 5000 logs rendered.
-        let skip_count = logs.len().saturating_sub(5000);
-
-        for log in logs.into_iter().skip(skip_count) {
-            let color = if log.contains("ERROR") {
-                RED
-            } else if log.contains("WARN") {
-                ORANGE
-            } else if log.contains("INFO") {
-                GREEN_SHAMROCK
-            } else if log.contains("DEBUG") {
-                BLUE
-            } else {
-                OFF_WHITE
-            };
+    log_column = log_column.push(Space::new().height(start as f32 * LOG_LINE_HEIGHT));
+    for log in rows {
+        let line = text(log.to_string())
+            .size(12)
+            .color(level_color(log.level))
+            .wrapping(Wrapping::None);
+        let line = container(line)
+            .width(Fill)
+            .height(LOG_LINE_HEIGHT)
+            .align_y(Center)
+            .clip(true);
+        let line = if log_view.selected.contains(&log.seq) {
+            line.style(selected_log())
+        } else {
+            line
+        };
 
-            log_column = log_column.push(text(log).size(12).color(color).wrapping(Wrapping::Glyph));
-        }
+        // Click a line to select it for copying.
+        log_column =
+            log_column.push(mouse_area(line).on_press(NodeMessage::ToggleLogSelected(log.seq)));
     }
+    log_column = log_column.push(Space::new().height((total - end) as f32 * LOG_LINE_HEIGHT));
 
-    let logs_scrollable = scrollable(log_column).height(Length::Fill).anchor_bottom();
+    let logs_scrollable = scrollable(log_column)
+        .height(Length::Fill)
+        .anchor_bottom()
+        .on_scroll(NodeMessage::LogsScrolled);
     let logs_container = container(logs_scrollable)
         .style(log_container())
         .padding(TITLE_PADDING)
         .height(Length::Fill)
         .width(Length::FillPortion(6));
 
-    let right = column![log_title, logs_container]
-        .width(Length::FillPortion(6))
-        .spacing(5);
+    let right = column![
+        sync_title,
+        sync_chart,
+        log_title,
+        log_filters,
+        logs_container
+    ]
+    .width(Length::FillPortion(6))
+    .spacing(5);
 
     row![left, right].spacing(20).into()
 }
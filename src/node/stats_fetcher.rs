@@ -14,10 +14,11 @@ use bitcoin::p2p::ServiceFlags;
 use regex::Regex;
 use tokio::sync::RwLock;
 
+use crate::node::error::BonsaiNodeError;
 use crate::node::message::NodeMessage;
 
 #[derive(Clone, Default, Debug)]
-pub(crate) enum NodeImpl {
+pub enum NodeImpl {
     Btcd,
     Core,
     Floresta,
@@ -42,27 +43,37 @@ impl Display for NodeImpl {
 
 #[allow(unused)]
 #[derive(Clone)]
-pub(crate) struct PeerInformation {
-    pub(crate) socket: SocketAddr,
-    pub(crate) services: ServiceFlags,
-    pub(crate) user_agent: String,
-    pub(crate) node_impl: NodeImpl,
-    pub(crate) initial_height: u32,
-    pub(crate) peer_status: PeerStatus,
-    pub(crate) connection_kind: ConnectionKind,
-    pub(crate) transport_protocol: TransportProtocol,
+pub struct PeerInformation {
+    pub socket: SocketAddr,
+    pub services: ServiceFlags,
+    pub user_agent: String,
+    pub node_impl: NodeImpl,
+    pub initial_height: u32,
+    pub peer_status: PeerStatus,
+    pub connection_kind: ConnectionKind,
+    pub transport_protocol: TransportProtocol,
 }
 
 #[derive(Clone)]
-pub(crate) struct NodeStatistics {
-    pub(crate) in_ibd: bool,
-    pub(crate) headers: u32,
-    pub(crate) blocks: u32,
-    pub(crate) accumulator: Stump,
-    pub(crate) accumulator_qr_data: Option<String>,
-    pub(crate) user_agent: String,
-    pub(crate) peer_informations: Vec<PeerInformation>,
-    pub(crate) uptime: Duration,
+pub struct NodeStatistics {
+    pub in_ibd: bool,
+    pub headers: u32,
+    pub blocks: u32,
+    pub accumulator: Stump,
+    pub accumulator_qr_data: Option<String>,
+    pub user_agent: String,
+    pub peer_informations: Vec<PeerInformation>,
+    pub uptime: Duration,
+    /// Resident memory of the process in bytes, if the platform exposes it.
+    pub memory: Option<u64>,
+    /// Private memory the process allocated in bytes, including the heap, if the platform exposes it.
+    pub allocated_memory: Option<u64>,
+    /// Whether the node validates the blocks skipped by its assumed accumulator.
+    pub backfill: bool,
+    /// Whether the node downloads compact block filters.
+    pub compact_filters: bool,
+    /// The highest height the node holds compact block filters up to, if any.
+    pub filter_height: Option<u32>,
 }
 
 fn encode_stump(stump: &Stump) -> String {
@@ -109,46 +120,117 @@ fn process_peer_infos(peer_infos: Vec<PeerInfo>) -> Vec<PeerInformation> {
     peer_informations
 }
 
-pub(crate) async fn fetch_stats(
+/// Resident memory of the process in bytes.
+pub fn resident_memory() -> Option<u64> {
+    process_memory("VmRSS:")
+}
+
+/// Private memory of the process in bytes: the heap and other anonymous mappings.
+pub fn allocated_memory() -> Option<u64> {
+    process_memory("VmData:")
+}
+
+/// A memory size of the process, read from `/proc/self/status`.
+#[cfg(target_os = "linux")]
+fn process_memory(field: &str) -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_memory(_field: &str) -> Option<u64> {
+    None
+}
+
+/// Gather [`NodeStatistics`] from a running node.
+pub async fn collect_stats(
     node_handle: Arc<RwLock<Node>>,
     start_time: Option<Instant>,
-) -> NodeMessage {
-    let result = async {
-        let node_handle = node_handle.read().await;
-
-        let in_ibd = node_handle.in_ibd().unwrap();
-        let headers = node_handle.get_height().unwrap_or(0);
-        let blocks = node_handle.get_validation_height().unwrap_or(0);
-        let accumulator = node_handle.get_accumulator().unwrap();
-        let user_agent = node_handle.get_config().await.unwrap().user_agent;
-        let uptime = start_time
-            .map(|t| t.elapsed())
-            .unwrap_or(Duration::from_secs(0));
-        let peer_infos_raw = node_handle.get_peer_info().await.unwrap_or_default();
-        let peer_informations = process_peer_infos(peer_infos_raw);
-
-        let encoded_stump = encode_stump(&accumulator);
-        let accumulator_qr_data = if !encoded_stump.is_empty() {
-            Some(encoded_stump)
-        } else {
-            None
-        };
+) -> Result<NodeStatistics, BonsaiNodeError> {
+    let node_handle = node_handle.read().await;
+
+    let in_ibd = node_handle
+        .in_ibd()
+        .map_err(|e| BonsaiNodeError::Generic(e.to_string()))?;
+    let headers = node_handle.get_height().unwrap_or(0);
+    let blocks = node_handle.get_validation_height().unwrap_or(0);
+    let accumulator = node_handle
+        .get_accumulator()
+        .map_err(|e| BonsaiNodeError::Generic(e.to_string()))?;
+    let config = node_handle.get_config().await?;
+    let filter_height = if config.compact_filters {
+        let start = config.filter_start_height.unwrap_or(0).max(0) as u32;
+        filter_height(&node_handle, start, headers)
+    } else {
+        None
+    };
+    let uptime = start_time
+        .map(|t| t.elapsed())
+        .unwrap_or(Duration::from_secs(0));
+    let peer_infos_raw = node_handle.get_peer_info().await.unwrap_or_default();
+    let peer_informations = process_peer_infos(peer_infos_raw);
+
+    let encoded_stump = encode_stump(&accumulator);
+    let accumulator_qr_data = if !encoded_stump.is_empty() {
+        Some(encoded_stump)
+    } else {
+        None
+    };
 
-        Ok(NodeStatistics {
-            in_ibd,
-            headers,
-            blocks,
-            accumulator,
-            user_agent,
-            accumulator_qr_data,
-            peer_informations,
-            uptime,
-        })
+    Ok(NodeStatistics {
+        in_ibd,
+        headers,
+        blocks,
+        accumulator,
+        user_agent: config.user_agent,
+        accumulator_qr_data,
+        peer_informations,
+        uptime,
+        memory: resident_memory(),
+        allocated_memory: allocated_memory(),
+        backfill: config.backfill,
+        compact_filters: config.compact_filters,
+        filter_height,
+    })
+}
+
+/// The highest height up to which the node holds compact block filters, from `start`.
+///
+/// Filters are downloaded in order, so the ones held are searched for the end of the run.
+fn filter_height(node: &Node, start: u32, tip: u32) -> Option<u32> {
+    let has_filter = |height| matches!(node.get_block_filter(height), Ok(Some(_)));
+    if start > tip || !has_filter(start) {
+        return None;
     }
-    .await;
 
-    match result {
+    let (mut synced, mut missing) = (start, tip + 1);
+    while missing - synced > 1 {
+        let height = synced + (missing - synced) / 2;
+        if has_filter(height) {
+            synced = height;
+        } else {
+            missing = height;
+        }
+    }
+    Some(synced)
+}
+
+pub async fn fetch_stats(
+    node_handle: Arc<RwLock<Node>>,
+    start_time: Option<Instant>,
+) -> NodeMessage {
+    match collect_stats(node_handle, start_time).await {
         Ok(stats) => NodeMessage::Statistics(stats),
-        Err(e) => NodeMessage::Error(e),
+        // The node no longer answers, so it is considered failed.
+        Err(e) => NodeMessage::Failed(e),
     }
 }
@@ -20,7 +20,7 @@ pub const TITLE_PADDING: Padding = Padding {
     left: 10.0,
 };
 
-pub(crate) fn title_container() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn title_container() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
@@ -32,7 +32,7 @@ pub(crate) fn title_container() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
-pub(crate) fn table_cell() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn table_cell() -> impl Fn(&Theme) -> ContainerStyle {
     move |_theme| {
         let border = Border {
             color: OFF_WHITE,
@@ -47,7 +47,7 @@ pub(crate) fn table_cell() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
-pub(crate) fn input_field() -> impl Fn(&Theme, iced::widget::text_input::Status) -> TextInputStyle {
+pub fn input_field() -> impl Fn(&Theme, iced::widget::text_input::Status) -> TextInputStyle {
     move |_theme, _status| TextInputStyle {
         background: iced::Background::Color(iced::Color::TRANSPARENT),
         border: Border {
@@ -62,7 +62,7 @@ pub(crate) fn input_field() -> impl Fn(&Theme, iced::widget::text_input::Status)
     }
 }
 
-pub(crate) fn transparent_button() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
+pub fn transparent_button() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
     move |_theme, _status| ButtonStyle {
         background: None,
         border: Border::default(),
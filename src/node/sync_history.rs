@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+/// The sync history, in the active network's data directory.
+pub const SYNC_HISTORY_FILE: &str = "sync_history.json";
+
+/// How many samples are kept before the history is thinned.
+const MAX_SAMPLES: usize = 1024;
+
+/// The interval between samples of a new history, in seconds.
+const SAMPLE_INTERVAL: i64 = 10;
+
+/// How far back the sync rate is measured, in seconds.
+const RATE_WINDOW: i64 = 300;
+
+/// How little of the [`RATE_WINDOW`] must be covered before a rate is given, in seconds.
+const MIN_RATE_SPAN: i64 = 30;
+
+/// How often the history is saved while the node runs, in seconds.
+const SAVE_INTERVAL: i64 = 60;
+
+/// The validated height and the headers known at a point in time.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SyncSample {
+    /// Unix time, in seconds.
+    pub time: i64,
+    pub blocks: u32,
+    pub headers: u32,
+}
+
+impl SyncSample {
+    /// How many headers are yet to be validated.
+    pub fn gap(&self) -> u32 {
+        self.headers.saturating_sub(self.blocks)
+    }
+}
+
+/// A time series of the node's sync progress, fed by every statistics poll.
+///
+/// Samples are kept every [`SAMPLE_INTERVAL`] seconds. Once there are more than
+/// [`MAX_SAMPLES`], every other one is dropped and the interval doubles, so the
+/// history spans the whole sync at a bounded size. It is saved to the active
+/// network's data directory, and picks up where it left off after a restart.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncHistory {
+    pub samples: VecDeque<SyncSample>,
+    /// The interval between samples, in seconds.
+    interval: i64,
+    /// Every poll within the [`RATE_WINDOW`], to measure the sync rate.
+    #[serde(skip)]
+    recent: VecDeque<SyncSample>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    saved_at: i64,
+}
+
+impl SyncHistory {
+    /// Load the history saved in `dir`, saving the current one first if it belongs elsewhere.
+    pub fn load(&mut self, dir: &Path) {
+        let path = dir.join(SYNC_HISTORY_FILE);
+        if self.path.as_ref() == Some(&path) {
+            return;
+        }
+        self.save();
+
+        let mut history = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Discarding unreadable {}: {e}", path.to_string_lossy());
+                SyncHistory::default()
+            }),
+            Err(_) => SyncHistory::default(),
+        };
+        history.path = Some(path);
+        history.saved_at = Utc::now().timestamp();
+        *self = history;
+    }
+
+    /// Record the node's progress, as of now.
+    pub fn record(&mut self, blocks: u32, headers: u32) {
+        let sample = SyncSample {
+            time: Utc::now().timestamp(),
+            blocks,
+            headers,
+        };
+
+        self.recent.push_back(sample);
+        while self
+            .recent
+            .front()
+            .is_some_and(|oldest| oldest.time < sample.time - RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+
+        let interval = self.interval.max(SAMPLE_INTERVAL);
+        if self
+            .samples
+            .back()
+            .is_none_or(|last| sample.time - last.time >= interval)
+        {
+            self.samples.push_back(sample);
+            if self.samples.len() > MAX_SAMPLES {
+                // Keep the newest sample, and every other one before it.
+                self.samples = self
+                    .samples
+                    .iter()
+                    .rev()
+                    .step_by(2)
+                    .rev()
+                    .copied()
+                    .collect();
+                self.interval = interval * 2;
+            }
+        }
+
+        if sample.time - self.saved_at >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    /// Forget the polls of a node that stopped, so the rate isn't measured across the downtime.
+    pub fn stopped(&mut self) {
+        self.recent.clear();
+        self.save();
+    }
+
+    /// Blocks validated per second over the last [`RATE_WINDOW`].
+    pub fn rate(&self) -> Option<f64> {
+        let (first, last) = (self.recent.front()?, self.recent.back()?);
+        let span = last.time - first.time;
+        if span < MIN_RATE_SPAN {
+            return None;
+        }
+
+        Some(last.blocks.saturating_sub(first.blocks) as f64 / span as f64)
+    }
+
+    /// How long validating the remaining headers takes at the current [`SyncHistory::rate`].
+    pub fn eta(&self) -> Option<Duration> {
+        let gap = self.recent.back()?.gap();
+        match self.rate()? {
+            rate if rate > 0.0 && gap > 0 => Some(Duration::from_secs_f64(gap as f64 / rate)),
+            _ => None,
+        }
+    }
+
+    pub fn save(&mut self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        self.saved_at = Utc::now().timestamp();
+
+        let result = serde_json::to_string(self)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&path, json)
+            });
+        if let Err(e) = result {
+            warn!("Failed to save {}: {e}", path.to_string_lossy());
+        }
+    }
+}
@@ -1,3 +1,3 @@
 use iced::Length;
 
-pub(crate) const ROOT_CELL_HEIGHT: Length = Length::Fixed(40.0);
+pub const ROOT_CELL_HEIGHT: Length = Length::Fixed(40.0);
@@ -1,7 +1,11 @@
 use std::fs;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use bdk_floresta::ChainParams;
 use bdk_floresta::UtreexoNodeConfig;
@@ -12,16 +16,46 @@ use serde::Deserialize;
 use serde::Serialize;
 use tracing::error;
 
-pub(crate) const AUTO_START_NODE: bool = false;
-pub(crate) const SETTINGS_FILE: &str = "bonsai.toml";
+use crate::common::log_file::LOG_MAX_FILES;
+use crate::common::log_file::LOG_MAX_SIZE;
+use crate::common::log_file::LogRotation;
+use crate::common::logger::LogLevel;
+use crate::common::logger::LogLevelTarget;
+use crate::common::logger::LogLevels;
+use crate::common::logger::set_log_levels;
+use crate::node::control::MAX_RETRY_ATTEMPTS;
+use crate::node::control::RestartPolicy;
+use crate::node::control::STALL_TIMEOUT;
+use crate::node::exporter::METRICS_PORT;
+use crate::node::exporter::METRICS_SERVER;
+use crate::node::rpc::RPC_SERVER;
+use crate::node::rpc::default_rpc_port;
+
+pub const AUTO_START_NODE: bool = false;
+pub const ELECTRUM_SERVER: bool = false;
+pub const SETTINGS_FILE: &str = "bonsai.toml";
+
+/// The base data directory, if overridden with `--datadir`.
+static BASE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// The settings file, if overridden with `--config`.
+static SETTINGS_PATH: OnceLock<PathBuf> = OnceLock::new();
 
 #[derive(Debug, Clone, Default)]
-pub(crate) enum BonsaiSettingsMessage {
+pub enum BonsaiSettingsMessage {
     // Application-wide settings.
     NetworkChanged(Network),
+    LogLevelChanged(LogLevelTarget, LogLevel),
 
     // Node specific settings.
     AutoStartChanged(bool),
+    RpcServerChanged(bool),
+    ElectrumServerChanged(bool),
+    RestAddressInputChanged(String),
+    EventSocketChanged(bool),
+    MetricsServerChanged(bool),
+    RestartPolicyChanged(RestartPolicy),
+    MaxRestartsChanged(String),
 
     // Network Specific Node Settings.
     UseAssumeUtreexoChanged(bool),
@@ -44,73 +78,107 @@ pub(crate) enum BonsaiSettingsMessage {
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
-pub(crate) struct BonsaiSettings {
+pub struct BonsaiSettings {
     #[serde(default)]
-    pub(crate) bonsai: BonsaiAppSettings,
+    pub bonsai: BonsaiAppSettings,
     #[serde(default)]
-    pub(crate) node: NodeSettings,
+    pub node: NodeSettings,
 
     /// Whether the node needs a restart to apply configuration changes.
     #[serde(skip)]
-    pub(crate) node_restart_required: bool,
+    pub node_restart_required: bool,
 
     /// Whether we have configuration changes that need to be saved.
     #[serde(skip)]
-    pub(crate) unsaved_changes: bool,
+    pub unsaved_changes: bool,
 
     #[serde(skip)]
-    pub(crate) user_agent_input: String,
+    pub user_agent_input: String,
     #[serde(skip)]
-    pub(crate) fixed_peer_input: String,
+    pub fixed_peer_input: String,
     #[serde(skip)]
-    pub(crate) proxy_input: String,
+    pub proxy_input: String,
+    #[serde(skip)]
+    pub rest_address_input: String,
+
+    /// Overrides from the command line, which are never saved.
+    #[serde(skip)]
+    pub overrides: SessionOverrides,
+}
+
+/// Settings overridden from the command line, for this session only.
+#[derive(Clone, Default)]
+pub struct SessionOverrides {
+    pub network: Option<Network>,
+    pub start_node: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
-pub(crate) struct BonsaiAppSettings {
-    pub(crate) network: Option<Network>,
+pub struct BonsaiAppSettings {
+    pub network: Option<Network>,
+    /// The size a log file may grow to before it is rotated, in MiB.
+    pub log_max_size: Option<u64>,
+    /// How many rotated log files are kept per network.
+    pub log_max_files: Option<usize>,
+    #[serde(default)]
+    pub log_levels: LogLevels,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
-pub(crate) struct NodeSettings {
-    pub(crate) auto_start: Option<bool>,
+pub struct NodeSettings {
+    pub auto_start: Option<bool>,
+    pub rpc_server: Option<bool>,
+    /// Defaults to the network's port in Bitcoin Core.
+    pub rpc_port: Option<u16>,
+    pub electrum_server: Option<bool>,
+    /// Defaults to the network's Electrum TCP port, on localhost.
+    pub electrum_address: Option<SocketAddr>,
+    /// The REST server is enabled while this is set.
+    pub rest_address: Option<SocketAddr>,
+    pub event_socket: Option<bool>,
+    pub metrics_server: Option<bool>,
+    pub metrics_port: Option<u16>,
+    pub restart_policy: Option<RestartPolicy>,
+    pub max_restarts: Option<u32>,
+    /// Minutes without progress before a node behind its peers is restarted.
+    pub stall_timeout: Option<u64>,
 
     #[serde(flatten)]
-    pub(crate) network_configs: NetworkConfigs,
+    pub network_configs: NetworkConfigs,
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
-pub(crate) struct NetworkConfigs {
+pub struct NetworkConfigs {
     #[serde(default)]
-    pub(crate) bitcoin: NodeNetworkSpecific,
+    pub bitcoin: NodeNetworkSpecific,
     #[serde(default)]
-    pub(crate) signet: NodeNetworkSpecific,
+    pub signet: NodeNetworkSpecific,
     #[serde(default)]
-    pub(crate) testnet3: NodeNetworkSpecific,
+    pub testnet3: NodeNetworkSpecific,
     #[serde(default)]
-    pub(crate) testnet4: NodeNetworkSpecific,
+    pub testnet4: NodeNetworkSpecific,
     #[serde(default)]
-    pub(crate) regtest: NodeNetworkSpecific,
+    pub regtest: NodeNetworkSpecific,
 }
 
 #[derive(Default, Clone, Serialize, Deserialize)]
-pub(crate) struct NodeNetworkSpecific {
-    pub(crate) use_assume_utreexo: Option<bool>,
-    pub(crate) pow_fraud_proofs: Option<bool>,
-    pub(crate) backfill: Option<bool>,
-    pub(crate) user_agent: Option<String>,
-    pub(crate) allow_v1_fallback: Option<bool>,
-    pub(crate) fixed_peer: Option<String>,
-    pub(crate) max_banscore: Option<u32>,
-    pub(crate) max_outbound: Option<u32>,
-    pub(crate) max_inflight: Option<u32>,
-    pub(crate) disable_dns_seeds: Option<bool>,
-    pub(crate) proxy: Option<SocketAddr>,
+pub struct NodeNetworkSpecific {
+    pub use_assume_utreexo: Option<bool>,
+    pub pow_fraud_proofs: Option<bool>,
+    pub backfill: Option<bool>,
+    pub user_agent: Option<String>,
+    pub allow_v1_fallback: Option<bool>,
+    pub fixed_peer: Option<String>,
+    pub max_banscore: Option<u32>,
+    pub max_outbound: Option<u32>,
+    pub max_inflight: Option<u32>,
+    pub disable_dns_seeds: Option<bool>,
+    pub proxy: Option<SocketAddr>,
 }
 
 impl NodeNetworkSpecific {
     /// Convert to UtreexoNodeConfig, using defaults where options are None
-    pub(crate) fn to_config(&self, network: Network, data_dir: PathBuf) -> UtreexoNodeConfig {
+    pub fn to_config(&self, network: Network, data_dir: PathBuf) -> UtreexoNodeConfig {
         let default = UtreexoNodeConfig {
             network,
             datadir: String::from(data_dir.to_string_lossy()),
@@ -144,7 +212,7 @@ impl NodeNetworkSpecific {
     }
 
     /// Create from UtreexoNodeConfig
-    pub(crate) fn from_config(config: &UtreexoNodeConfig) -> Self {
+    pub fn from_config(config: &UtreexoNodeConfig) -> Self {
         NodeNetworkSpecific {
             use_assume_utreexo: Some(config.assume_utreexo.is_some()),
             pow_fraud_proofs: Some(config.pow_fraud_proofs),
@@ -163,7 +231,7 @@ impl NodeNetworkSpecific {
 
 impl NodeSettings {
     /// Get the network config for a given network
-    pub(crate) fn get_network_config(&self, network: Network) -> &NodeNetworkSpecific {
+    pub fn get_network_config(&self, network: Network) -> &NodeNetworkSpecific {
         match network {
             Network::Bitcoin => &self.network_configs.bitcoin,
             Network::Signet => &self.network_configs.signet,
@@ -174,7 +242,7 @@ impl NodeSettings {
     }
 
     /// Get mutable network config for a given network
-    pub(crate) fn get_network_config_mut(&mut self, network: Network) -> &mut NodeNetworkSpecific {
+    pub fn get_network_config_mut(&mut self, network: Network) -> &mut NodeNetworkSpecific {
         match network {
             Network::Bitcoin => &mut self.network_configs.bitcoin,
             Network::Signet => &mut self.network_configs.signet,
@@ -186,15 +254,129 @@ impl NodeSettings {
 }
 
 impl BonsaiSettings {
-    pub(crate) fn base_dir() -> PathBuf {
-        dirs::home_dir()
-            .expect("Could not find home")
-            .join(".bonsai")
+    /// Override the base data directory and the settings file for this session.
+    ///
+    /// Must be called before anything reads them.
+    pub fn set_paths(base_dir: Option<PathBuf>, settings_path: Option<PathBuf>) {
+        if let Some(base_dir) = base_dir {
+            let _ = BASE_DIR.set(base_dir);
+        }
+        if let Some(settings_path) = settings_path {
+            let _ = SETTINGS_PATH.set(settings_path);
+        }
+    }
+
+    pub fn base_dir() -> PathBuf {
+        BASE_DIR.get().cloned().unwrap_or_else(|| {
+            dirs::home_dir()
+                .expect("Could not find home")
+                .join(".bonsai")
+        })
+    }
+
+    /// Path to the settings file.
+    pub fn path() -> PathBuf {
+        SETTINGS_PATH
+            .get()
+            .cloned()
+            .unwrap_or_else(|| Self::base_dir().join(SETTINGS_FILE))
+    }
+
+    /// The active network: the session's override, else the saved one.
+    pub fn network(&self) -> Network {
+        self.overrides
+            .network
+            .or(self.bonsai.network)
+            .unwrap_or(Network::Signet)
+    }
+
+    /// The active network's data directory, holding its node data and logs.
+    pub fn data_dir(&self) -> PathBuf {
+        Self::base_dir().join(self.network().to_string())
+    }
+
+    pub fn log_rotation(&self) -> LogRotation {
+        LogRotation {
+            max_size: self.bonsai.log_max_size.unwrap_or(LOG_MAX_SIZE) * 1024 * 1024,
+            max_files: self.bonsai.log_max_files.unwrap_or(LOG_MAX_FILES),
+        }
+    }
+
+    /// Whether to start the node on launch.
+    pub fn auto_start_node(&self) -> bool {
+        self.overrides.start_node || self.node.auto_start.unwrap_or(AUTO_START_NODE)
+    }
+
+    /// The JSON-RPC server's port, if the server is enabled.
+    pub fn rpc_port(&self) -> Option<u16> {
+        if !self.node.rpc_server.unwrap_or(RPC_SERVER) {
+            return None;
+        }
+
+        Some(
+            self.node
+                .rpc_port
+                .unwrap_or_else(|| default_rpc_port(self.network())),
+        )
+    }
+
+    /// The metrics exporter's port, if the exporter is enabled.
+    pub fn metrics_port(&self) -> Option<u16> {
+        if !self.node.metrics_server.unwrap_or(METRICS_SERVER) {
+            return None;
+        }
+
+        Some(self.node.metrics_port.unwrap_or(METRICS_PORT))
+    }
+
+    pub fn restart_policy(&self) -> RestartPolicy {
+        self.node.restart_policy.unwrap_or_default()
+    }
+
+    pub fn max_restarts(&self) -> u32 {
+        self.node.max_restarts.unwrap_or(MAX_RETRY_ATTEMPTS)
+    }
+
+    pub fn stall_timeout(&self) -> Duration {
+        Duration::from_secs(self.node.stall_timeout.unwrap_or(STALL_TIMEOUT) * 60)
+    }
+
+    /// The Electrum server's address, if the server is enabled.
+    pub fn electrum_address(&self) -> Option<SocketAddr> {
+        if !self.node.electrum_server.unwrap_or(ELECTRUM_SERVER) {
+            return None;
+        }
+
+        Some(self.node.electrum_address.unwrap_or_else(|| {
+            SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                default_electrum_port(self.network()),
+            )
+        }))
+    }
+
+    /// Apply overrides from the command line, without touching the saved settings.
+    pub fn apply_overrides(&mut self, overrides: SessionOverrides) {
+        self.overrides = overrides;
+        self.reset_inputs();
+    }
+
+    /// Initialize input fields with the active network's values.
+    fn reset_inputs(&mut self) {
+        let config = self.node.get_network_config(self.network());
+        self.user_agent_input = config.user_agent.clone().unwrap_or_default();
+        self.fixed_peer_input = config.fixed_peer.clone().unwrap_or_default();
+        self.proxy_input = config.proxy.map(|p| p.to_string()).unwrap_or_default();
+        self.rest_address_input = self
+            .node
+            .rest_address
+            .map(|address| address.to_string())
+            .unwrap_or_default();
     }
 
     /// Load settings from disk, or return default if file doesn't exist
-    pub(crate) fn load() -> Self {
-        let path = Self::base_dir().join(SETTINGS_FILE);
+    pub fn load() -> Self {
+        let path = Self::path();
 
         if !path.exists() {
             return Self::default();
@@ -206,11 +388,7 @@ impl BonsaiSettings {
                 settings.node_restart_required = false;
 
                 // Initialize input fields with current values
-                let network = settings.bonsai.network.unwrap_or(Network::Signet);
-                let config = settings.node.get_network_config(network);
-                settings.user_agent_input = config.user_agent.clone().unwrap_or_default();
-                settings.fixed_peer_input = config.fixed_peer.clone().unwrap_or_default();
-                settings.proxy_input = config.proxy.map(|p| p.to_string()).unwrap_or_default();
+                settings.reset_inputs();
 
                 settings
             }
@@ -219,11 +397,14 @@ impl BonsaiSettings {
     }
 
     /// Save settings to disk
-    pub(crate) fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let data_directory = Self::base_dir();
-        let settings_path = data_directory.join(SETTINGS_FILE);
-
-        match fs::create_dir_all(Self::base_dir()) {
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let settings_path = Self::path();
+        let data_directory = settings_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(Self::base_dir);
+
+        match fs::create_dir_all(&data_directory) {
             Ok(_) => {}
             Err(e) => {
                 error!(
@@ -250,8 +431,12 @@ impl BonsaiSettings {
     }
 
     /// Get the [`UtreexoNodeConfig`] for starting the node.
-    pub(crate) fn get_node_config(&self, network: Network, data_dir: &Path) -> UtreexoNodeConfig {
-        let network = self.bonsai.network.unwrap_or(network);
+    pub fn get_node_config(&self, network: Network, data_dir: &Path) -> UtreexoNodeConfig {
+        let network = self
+            .overrides
+            .network
+            .or(self.bonsai.network)
+            .unwrap_or(network);
         let data_dir = data_dir.join(network.to_string());
 
         let network_config = self.node.get_network_config(network);
@@ -259,22 +444,27 @@ impl BonsaiSettings {
     }
 
     /// Update settings from a UtreexoNodeConfig (called after first run)
-    pub(crate) fn update_from_config(&mut self, config: &UtreexoNodeConfig) {
-        self.bonsai.network = Some(config.network);
+    pub fn update_from_config(&mut self, config: &UtreexoNodeConfig) {
+        // A network given on the command line is only for this session.
+        if self.overrides.network.is_none() {
+            self.bonsai.network = Some(config.network);
+        }
 
         let network_config = self.node.get_network_config_mut(config.network);
         *network_config = NodeNetworkSpecific::from_config(config);
     }
 
-    pub(crate) fn view(&self) -> Element<'_, BonsaiSettingsMessage> {
+    pub fn view(&self) -> Element<'_, BonsaiSettingsMessage> {
         use crate::settings::view::view_settings;
         view_settings(self)
     }
 
-    pub(crate) fn update(&mut self, message: BonsaiSettingsMessage) -> Task<BonsaiSettingsMessage> {
+    pub fn update(&mut self, message: BonsaiSettingsMessage) -> Task<BonsaiSettingsMessage> {
         match message {
             BonsaiSettingsMessage::NetworkChanged(network) => {
-                if self.bonsai.network != Some(network) {
+                if self.network() != network {
+                    // An explicit choice replaces the session's override.
+                    self.overrides.network = None;
                     self.bonsai.network = Some(network);
                     self.node_restart_required = true;
                     self.unsaved_changes = true;
@@ -288,8 +478,26 @@ impl BonsaiSettings {
                 Task::none()
             }
 
+            BonsaiSettingsMessage::RpcServerChanged(enabled) => {
+                if self.node.rpc_server != Some(enabled) {
+                    self.node.rpc_server = Some(enabled);
+                    self.node_restart_required = true;
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
+            BonsaiSettingsMessage::ElectrumServerChanged(enabled) => {
+                if self.node.electrum_server != Some(enabled) {
+                    self.node.electrum_server = Some(enabled);
+                    self.node_restart_required = true;
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
             BonsaiSettingsMessage::UseAssumeUtreexoChanged(enabled) => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
                 if config.use_assume_utreexo != Some(enabled) {
                     config.use_assume_utreexo = Some(enabled);
@@ -300,7 +508,7 @@ impl BonsaiSettings {
             }
 
             BonsaiSettingsMessage::PowFraudProofsChanged(enabled) => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
                 if config.pow_fraud_proofs != Some(enabled) {
                     config.pow_fraud_proofs = Some(enabled);
@@ -311,7 +519,7 @@ impl BonsaiSettings {
             }
 
             BonsaiSettingsMessage::BackfillChanged(enabled) => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
                 if config.backfill != Some(enabled) {
                     config.backfill = Some(enabled);
@@ -327,7 +535,7 @@ impl BonsaiSettings {
             }
 
             BonsaiSettingsMessage::AllowV1FallbackChanged(enabled) => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
                 if config.allow_v1_fallback != Some(enabled) {
                     config.allow_v1_fallback = Some(enabled);
@@ -347,9 +555,32 @@ impl BonsaiSettings {
                 Task::none()
             }
 
+            BonsaiSettingsMessage::MetricsServerChanged(enabled) => {
+                if self.node.metrics_server != Some(enabled) {
+                    self.node.metrics_server = Some(enabled);
+                    self.node_restart_required = true;
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
+            BonsaiSettingsMessage::EventSocketChanged(enabled) => {
+                if self.node.event_socket != Some(enabled) {
+                    self.node.event_socket = Some(enabled);
+                    self.node_restart_required = true;
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
+            BonsaiSettingsMessage::RestAddressInputChanged(value) => {
+                self.rest_address_input = value;
+                Task::none()
+            }
+
             BonsaiSettingsMessage::MaxBanscoreChanged(value) => {
                 if let Ok(banscore) = value.parse::<u32>() {
-                    let network = self.bonsai.network.unwrap_or(Network::Signet);
+                    let network = self.network();
                     let config = self.node.get_network_config_mut(network);
                     if config.max_banscore != Some(banscore) {
                         config.max_banscore = Some(banscore);
@@ -362,7 +593,7 @@ impl BonsaiSettings {
 
             BonsaiSettingsMessage::MaxOutboundChanged(value) => {
                 if let Ok(outbound) = value.parse::<u32>() {
-                    let network = self.bonsai.network.unwrap_or(Network::Signet);
+                    let network = self.network();
                     let config = self.node.get_network_config_mut(network);
                     if config.max_outbound != Some(outbound) {
                         config.max_outbound = Some(outbound);
@@ -375,7 +606,7 @@ impl BonsaiSettings {
 
             BonsaiSettingsMessage::MaxInflightChanged(value) => {
                 if let Ok(inflight) = value.parse::<u32>() {
-                    let network = self.bonsai.network.unwrap_or(Network::Signet);
+                    let network = self.network();
                     let config = self.node.get_network_config_mut(network);
                     if config.max_inflight != Some(inflight) {
                         config.max_inflight = Some(inflight);
@@ -386,8 +617,37 @@ impl BonsaiSettings {
                 Task::none()
             }
 
+            // Applied right away, without a restart.
+            BonsaiSettingsMessage::LogLevelChanged(target, level) => {
+                if self.bonsai.log_levels.level(target) != level {
+                    self.bonsai.log_levels.set_level(target, level);
+                    set_log_levels(&self.bonsai.log_levels);
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
+            // The supervisor picks these up without a restart.
+            BonsaiSettingsMessage::RestartPolicyChanged(policy) => {
+                if self.node.restart_policy != Some(policy) {
+                    self.node.restart_policy = Some(policy);
+                    self.unsaved_changes = true;
+                }
+                Task::none()
+            }
+
+            BonsaiSettingsMessage::MaxRestartsChanged(value) => {
+                if let Ok(max_restarts) = value.parse::<u32>() {
+                    if self.node.max_restarts != Some(max_restarts) {
+                        self.node.max_restarts = Some(max_restarts);
+                        self.unsaved_changes = true;
+                    }
+                }
+                Task::none()
+            }
+
             BonsaiSettingsMessage::DisableDnsSeedsChanged(enabled) => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
                 if config.disable_dns_seeds != Some(enabled) {
                     config.disable_dns_seeds = Some(enabled);
@@ -398,7 +658,7 @@ impl BonsaiSettings {
             }
 
             BonsaiSettingsMessage::SaveSettings => {
-                let network = self.bonsai.network.unwrap_or(Network::Signet);
+                let network = self.network();
                 let config = self.node.get_network_config_mut(network);
 
                 if !self.user_agent_input.is_empty()
@@ -443,6 +703,22 @@ impl BonsaiSettings {
                     self.node_restart_required = true;
                 }
 
+                let rest_address = if self.rest_address_input.is_empty() {
+                    None
+                } else {
+                    match self.rest_address_input.parse::<SocketAddr>() {
+                        Ok(addr) => Some(addr),
+                        Err(e) => {
+                            error!("Invalid REST address '{}': {}", self.rest_address_input, e);
+                            None
+                        }
+                    }
+                };
+                if self.node.rest_address != rest_address {
+                    self.node.rest_address = rest_address;
+                    self.node_restart_required = true;
+                }
+
                 if self.save().is_ok() {
                     self.unsaved_changes = false;
                 }
@@ -462,3 +738,14 @@ impl BonsaiSettings {
         }
     }
 }
+
+/// The Electrum TCP port conventionally used on `network`.
+pub fn default_electrum_port(network: Network) -> u16 {
+    match network {
+        Network::Bitcoin => 50001,
+        Network::Testnet => 60001,
+        Network::Testnet4 => 40001,
+        Network::Regtest => 60401,
+        _ => 60601,
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use bitcoin::Network;
+use clap::Parser;
+
+use crate::settings::bonsai_settings::BonsaiSettings;
+use crate::settings::bonsai_settings::SessionOverrides;
+
+/// Command-line arguments, which override [`BonsaiSettings`] for this session
+/// without rewriting the saved settings file.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Network to run on: bitcoin, signet, testnet4 or regtest.
+    #[arg(long, value_parser = parse_network)]
+    pub network: Option<Network>,
+
+    /// Base data directory [default: ~/.bonsai].
+    #[arg(long, value_name = "DIR")]
+    pub datadir: Option<PathBuf>,
+
+    /// Settings file [default: <DATADIR>/bonsai.toml].
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Start the node on launch, regardless of the auto-start setting.
+    #[arg(long)]
+    pub start_node: bool,
+
+    /// Log filter, e.g. `debug` or `info,bdk_floresta=debug` [default: $RUST_LOG].
+    #[arg(long, value_name = "FILTER")]
+    pub log_level: Option<String>,
+
+    /// Don't load the GeoIP databases.
+    #[arg(long)]
+    pub no_geoip: bool,
+}
+
+/// Parse one of the networks Bonsai keeps settings for, which leaves out testnet3.
+fn parse_network(network: &str) -> Result<Network, String> {
+    match network.parse::<Network>() {
+        Ok(Network::Testnet) | Err(_) => Err(format!(
+            "unsupported network '{network}', expected one of: bitcoin, signet, testnet4, regtest"
+        )),
+        Ok(network) => Ok(network),
+    }
+}
+
+impl Cli {
+    /// Set the session's data directory and settings file.
+    ///
+    /// Must be called before anything reads them.
+    pub fn set_paths(&self) {
+        BonsaiSettings::set_paths(self.datadir.clone(), self.config.clone());
+    }
+
+    pub fn overrides(&self) -> SessionOverrides {
+        SessionOverrides {
+            network: self.network,
+            start_node: self.start_node,
+        }
+    }
+}
@@ -1,2 +1,3 @@
-pub(crate) mod bonsai_settings;
-pub(crate) mod view;
+pub mod bonsai_settings;
+pub mod cli;
+pub mod view;
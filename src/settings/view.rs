@@ -1,4 +1,3 @@
-use bdk_floresta::UtreexoNodeConfig;
 use bitcoin::Network;
 use iced::Alignment::Center;
 use iced::Background::Color as BackgroundColor;
@@ -39,21 +38,29 @@ use crate::common::interface::constants::TABLE_CELL_FONT_SIZE;
 use crate::common::interface::container::button_container;
 use crate::common::interface::shadow::SHADOW_GRAY;
 use crate::common::interface::shadow::SHADOW_RED;
+use crate::common::logger::LogLevel;
+use crate::common::logger::LogLevelTarget;
+use crate::node::control::RestartPolicy;
+use crate::node::events::EVENT_SOCKET;
+use crate::node::exporter::METRICS_SERVER;
+use crate::node::rpc::RPC_SERVER;
 use crate::node::style::title_container;
 use crate::settings::bonsai_settings::AUTO_START_NODE;
 use crate::settings::bonsai_settings::BonsaiSettings;
 use crate::settings::bonsai_settings::BonsaiSettingsMessage;
+use crate::settings::bonsai_settings::ELECTRUM_SERVER;
 
 const SECTION_BOX_HEIGHT: f32 = 30.0;
 
-pub(crate) fn view_settings(settings: &BonsaiSettings) -> Element<'_, BonsaiSettingsMessage> {
-    let utreexo_node_config_default = UtreexoNodeConfig::default();
-
+pub fn view_settings(settings: &BonsaiSettings) -> Element<'_, BonsaiSettingsMessage> {
     let auto_start = settings.node.auto_start.unwrap_or(AUTO_START_NODE);
-    let active_network = settings
-        .bonsai
-        .network
-        .unwrap_or(utreexo_node_config_default.network);
+    let rpc_server = settings.node.rpc_server.unwrap_or(RPC_SERVER);
+    let electrum_server = settings.node.electrum_server.unwrap_or(ELECTRUM_SERVER);
+    let event_socket = settings.node.event_socket.unwrap_or(EVENT_SOCKET);
+    let metrics_server = settings.node.metrics_server.unwrap_or(METRICS_SERVER);
+    let restart_policy = settings.restart_policy();
+    let max_restarts = settings.max_restarts();
+    let active_network = settings.network();
 
     let node_config = settings.node.get_network_config(active_network);
 
@@ -442,6 +449,249 @@ pub(crate) fn view_settings(settings: &BonsaiSettings) -> Element<'_, BonsaiSett
     .padding(10);
     let max_inflight_section = column![max_inflight_title, max_inflight_controls];
 
+    let restart_policy_title: Container<'_, BonsaiSettingsMessage> =
+        container(text("RESTART POLICY").size(21));
+    let restart_policy_buttons = container(
+        row![
+            boolean_button_with_disable_logic(
+                "NEVER",
+                true,
+                restart_policy == RestartPolicy::Never,
+                RED,
+                BonsaiSettingsMessage::RestartPolicyChanged(RestartPolicy::Never)
+            ),
+            boolean_button_with_disable_logic(
+                "ON FAILURE",
+                true,
+                restart_policy == RestartPolicy::OnFailure,
+                YELLOW,
+                BonsaiSettingsMessage::RestartPolicyChanged(RestartPolicy::OnFailure)
+            ),
+            boolean_button_with_disable_logic(
+                "ALWAYS",
+                true,
+                restart_policy == RestartPolicy::Always,
+                GREEN_SHAMROCK,
+                BonsaiSettingsMessage::RestartPolicyChanged(RestartPolicy::Always)
+            ),
+        ]
+        .height(Length::Fixed(SECTION_BOX_HEIGHT))
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let restart_policy_section = column![restart_policy_title, restart_policy_buttons];
+
+    let max_restarts_title: Container<'_, BonsaiSettingsMessage> =
+        container(text("MAX RESTARTS").size(21));
+    let max_restarts_controls = container(
+        row![
+            container(
+                text(max_restarts.to_string())
+                    .align_x(Center)
+                    .align_y(Center)
+                    .size(16)
+            )
+            .padding(10)
+            .width(FillPortion(2))
+            .align_x(Center)
+            .align_y(Center)
+            .style(table_cell_with_shadow()),
+            button(text("-").size(16).align_x(Center).align_y(Center))
+                .on_press_maybe(if max_restarts > 0 {
+                    Some(BonsaiSettingsMessage::MaxRestartsChanged(
+                        (max_restarts - 1).to_string(),
+                    ))
+                } else {
+                    None
+                })
+                .width(FillPortion(1))
+                .style(button_container()),
+            button(text("+").size(16).align_x(Center).align_y(Center))
+                .on_press_maybe(if max_restarts < 100 {
+                    Some(BonsaiSettingsMessage::MaxRestartsChanged(
+                        (max_restarts + 1).to_string(),
+                    ))
+                } else {
+                    None
+                })
+                .width(FillPortion(1))
+                .style(button_container()),
+        ]
+        .spacing(10)
+        .height(Length::Fixed(SECTION_BOX_HEIGHT)),
+    )
+    .style(title_container())
+    .padding(10);
+    let max_restarts_section = column![max_restarts_title, max_restarts_controls];
+
+    let rpc_server_title: Container<'_, BonsaiSettingsMessage> = container(
+        text(match settings.rpc_port() {
+            Some(port) => format!("JSON-RPC SERVER [127.0.0.1:{port}]"),
+            None => "JSON-RPC SERVER".to_string(),
+        })
+        .size(21),
+    );
+    let rpc_server_buttons = container(
+        row![
+            boolean_button_with_disable_logic(
+                "TRUE",
+                true,
+                rpc_server,
+                GREEN_SHAMROCK,
+                BonsaiSettingsMessage::RpcServerChanged(true)
+            ),
+            boolean_button_with_disable_logic(
+                "FALSE",
+                false,
+                rpc_server,
+                RED,
+                BonsaiSettingsMessage::RpcServerChanged(false)
+            ),
+        ]
+        .height(Length::Fixed(SECTION_BOX_HEIGHT))
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let rpc_server_section = column![rpc_server_title, rpc_server_buttons];
+
+    let electrum_server_title: Container<'_, BonsaiSettingsMessage> = container(
+        text(match settings.electrum_address() {
+            Some(address) => format!("ELECTRUM SERVER [{address}]"),
+            None => "ELECTRUM SERVER".to_string(),
+        })
+        .size(21),
+    );
+    let electrum_server_buttons = container(
+        row![
+            boolean_button_with_disable_logic(
+                "TRUE",
+                true,
+                electrum_server,
+                GREEN_SHAMROCK,
+                BonsaiSettingsMessage::ElectrumServerChanged(true)
+            ),
+            boolean_button_with_disable_logic(
+                "FALSE",
+                false,
+                electrum_server,
+                RED,
+                BonsaiSettingsMessage::ElectrumServerChanged(false)
+            ),
+        ]
+        .height(Length::Fixed(SECTION_BOX_HEIGHT))
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let electrum_server_section = column![electrum_server_title, electrum_server_buttons];
+
+    let rest_server_title: Container<'_, BonsaiSettingsMessage> =
+        container(text("REST SERVER").size(21));
+    let rest_server_input = container(
+        text_input(
+            &settings
+                .node
+                .rest_address
+                .map(|address| address.to_string())
+                .unwrap_or_else(|| "DISABLED".to_string()),
+            &settings.rest_address_input,
+        )
+        .on_input(BonsaiSettingsMessage::RestAddressInputChanged)
+        .padding(10)
+        .width(Fill),
+    )
+    .style(title_container())
+    .padding(1);
+    let rest_server_section = column![rest_server_title, rest_server_input];
+
+    let event_socket_title: Container<'_, BonsaiSettingsMessage> =
+        container(text("EVENT SOCKET").size(21));
+    let event_socket_buttons = container(
+        row![
+            boolean_button_with_disable_logic(
+                "TRUE",
+                true,
+                event_socket,
+                GREEN_SHAMROCK,
+                BonsaiSettingsMessage::EventSocketChanged(true)
+            ),
+            boolean_button_with_disable_logic(
+                "FALSE",
+                false,
+                event_socket,
+                RED,
+                BonsaiSettingsMessage::EventSocketChanged(false)
+            ),
+        ]
+        .height(Length::Fixed(SECTION_BOX_HEIGHT))
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let event_socket_section = column![event_socket_title, event_socket_buttons];
+
+    let metrics_server_title: Container<'_, BonsaiSettingsMessage> = container(
+        text(match settings.metrics_port() {
+            Some(port) => format!("METRICS EXPORTER [127.0.0.1:{port}]"),
+            None => "METRICS EXPORTER".to_string(),
+        })
+        .size(21),
+    );
+    let metrics_server_buttons = container(
+        row![
+            boolean_button_with_disable_logic(
+                "TRUE",
+                true,
+                metrics_server,
+                GREEN_SHAMROCK,
+                BonsaiSettingsMessage::MetricsServerChanged(true)
+            ),
+            boolean_button_with_disable_logic(
+                "FALSE",
+                false,
+                metrics_server,
+                RED,
+                BonsaiSettingsMessage::MetricsServerChanged(false)
+            ),
+        ]
+        .height(Length::Fixed(SECTION_BOX_HEIGHT))
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let metrics_server_section = column![metrics_server_title, metrics_server_buttons];
+
+    // Each button cycles its target through the levels.
+    let log_levels_title: Container<'_, BonsaiSettingsMessage> =
+        container(text("LOG LEVELS").size(21));
+    let mut log_levels_buttons = row![].spacing(10);
+    for target in LogLevelTarget::TARGETS {
+        let level = settings.bonsai.log_levels.level(target);
+        log_levels_buttons = log_levels_buttons.push(
+            button(
+                column![
+                    text(target.to_string()).size(11),
+                    text(level.to_string())
+                        .size(11)
+                        .color(log_level_color(level)),
+                ]
+                .width(Fill)
+                .align_x(Center),
+            )
+            .on_press(BonsaiSettingsMessage::LogLevelChanged(target, level.next()))
+            .width(Fill)
+            .padding(2)
+            .style(button_container()),
+        );
+    }
+    let log_levels_buttons =
+        container(log_levels_buttons.height(Length::Fixed(SECTION_BOX_HEIGHT)))
+            .style(title_container())
+            .padding(10);
+    let log_levels_section = column![log_levels_title, log_levels_buttons];
+
     let save_button_row = row![
         text(if settings.unsaved_changes {
             "UNSAVED CHANGES"
@@ -545,11 +795,25 @@ pub(crate) fn view_settings(settings: &BonsaiSettings) -> Element<'_, BonsaiSett
     .spacing(15)
     .width(FillPortion(1));
 
-    row![left, right].spacing(20).into()
+    // Servers exposing the node and wallet to other programs, logging, and how the node is kept running.
+    let services = column![
+        rpc_server_section,
+        electrum_server_section,
+        rest_server_section,
+        event_socket_section,
+        metrics_server_section,
+        log_levels_section,
+        restart_policy_section,
+        max_restarts_section,
+    ]
+    .spacing(15)
+    .width(FillPortion(1));
+
+    row![left, services, right].spacing(20).into()
 }
 
 /// [`Button`] style for the [`Network`] toggle section.
-pub(crate) fn network_button_style(
+pub fn network_button_style(
     button_network: Network,
     active_network: Network,
     color: Color,
@@ -614,7 +878,7 @@ fn network_button_with_disable_logic<'a>(
     }
 }
 
-pub(crate) fn boolean_button_style(
+pub fn boolean_button_style(
     button_value: bool,
     active_value: bool,
     color: iced::Color,
@@ -655,6 +919,17 @@ pub(crate) fn boolean_button_style(
     }
 }
 
+fn log_level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Off => OFF_WHITE.scale_alpha(0.5),
+        LogLevel::Error => RED,
+        LogLevel::Warn => ORANGE,
+        LogLevel::Info => GREEN_SHAMROCK,
+        LogLevel::Debug => BLUE,
+        LogLevel::Trace => OFF_WHITE,
+    }
+}
+
 fn boolean_button_with_disable_logic<'a>(
     label: &'static str,
     button_value: bool,
@@ -675,7 +950,7 @@ fn boolean_button_with_disable_logic<'a>(
     }
 }
 
-pub(crate) fn table_cell_with_shadow() -> impl Fn(&Theme) -> ContainerStyle {
+pub fn table_cell_with_shadow() -> impl Fn(&Theme) -> ContainerStyle {
     |_theme| ContainerStyle {
         border: Border {
             color: OFF_WHITE,
@@ -687,7 +962,7 @@ pub(crate) fn table_cell_with_shadow() -> impl Fn(&Theme) -> ContainerStyle {
     }
 }
 
-pub(crate) fn delete_button_container() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
+pub fn delete_button_container() -> impl Fn(&Theme, ButtonStatus) -> ButtonStyle {
     |_theme, status| {
         let text_color = match status {
             ButtonStatus::Hovered => RED.scale_alpha(0.7),
@@ -0,0 +1,57 @@
+use bitcoin::Amount;
+use bitcoin::Block;
+use bitcoin::SignedAmount;
+use bitcoin::Txid;
+
+/// How many [`WalletEvent`]s are kept in the activity feed.
+pub(crate) const MAX_ACTIVITY: usize = 100;
+
+/// A wallet transaction seen in a block.
+#[derive(Clone, Debug)]
+pub(crate) struct WalletEvent {
+    pub(crate) txid: Txid,
+    pub(crate) height: u32,
+    pub(crate) sent: Amount,
+    pub(crate) received: Amount,
+}
+
+impl WalletEvent {
+    /// The net effect of the transaction on the wallet's balance.
+    pub(crate) fn net(&self) -> SignedAmount {
+        self.received.to_signed().unwrap_or(SignedAmount::MAX)
+            - self.sent.to_signed().unwrap_or(SignedAmount::MAX)
+    }
+
+    pub(crate) fn is_incoming(&self) -> bool {
+        self.net() > SignedAmount::ZERO
+    }
+
+    /// Confirmations of the transaction, given the wallet's tip height.
+    pub(crate) fn confirmations(&self, tip_height: u32) -> u32 {
+        tip_height.saturating_sub(self.height) + 1
+    }
+}
+
+/// Every transaction of a [`Block`] that pays to or spends from the wallet.
+///
+/// Must be called after the block has been applied to the wallet.
+pub(crate) fn wallet_events(
+    wallet: &bdk_wallet::Wallet,
+    block: &Block,
+    height: u32,
+) -> Vec<WalletEvent> {
+    block
+        .txdata
+        .iter()
+        .filter(|tx| wallet.get_tx(tx.compute_txid()).is_some())
+        .map(|tx| {
+            let (sent, received) = wallet.sent_and_received(tx);
+            WalletEvent {
+                txid: tx.compute_txid(),
+                height,
+                sent,
+                received,
+            }
+        })
+        .collect()
+}
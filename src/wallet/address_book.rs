@@ -0,0 +1,156 @@
+use core::fmt::Display;
+use core::str::FromStr;
+
+use bitcoin::Address;
+use bitcoin::Amount;
+use bitcoin::Denomination;
+use bitcoin::Network;
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::wallet::error::BonsaiWalletError;
+
+const BIP21_SCHEME: &str = "bitcoin:";
+
+/// An address book entry.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Contact {
+    pub(crate) name: String,
+    /// Addresses or BIP21 URIs this contact can be paid at.
+    pub(crate) destinations: Vec<String>,
+    #[serde(default)]
+    pub(crate) notes: String,
+}
+
+impl Contact {
+    /// Every destination of this contact, as a [`ContactDestination`].
+    pub(crate) fn contact_destinations(&self) -> impl Iterator<Item = ContactDestination> + '_ {
+        self.destinations
+            .iter()
+            .map(|destination| ContactDestination {
+                name: self.name.clone(),
+                destination: destination.clone(),
+            })
+    }
+}
+
+/// A single destination of a [`Contact`], as listed in the `SEND` section.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ContactDestination {
+    pub(crate) name: String,
+    pub(crate) destination: String,
+}
+
+impl Display for ContactDestination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.destination)
+    }
+}
+
+/// A parsed address or BIP21 URI.
+#[derive(Clone, Debug)]
+pub(crate) struct PaymentDestination {
+    pub(crate) address: Address,
+    pub(crate) amount: Option<Amount>,
+    pub(crate) label: Option<String>,
+}
+
+impl PaymentDestination {
+    /// Parse an address or a BIP21 URI, checking it against a [`Network`].
+    pub(crate) fn parse(destination: &str, network: Network) -> Result<Self, BonsaiWalletError> {
+        let destination = destination.trim();
+
+        let (address, query) = match destination
+            .get(..BIP21_SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(BIP21_SCHEME))
+        {
+            Some(_) => {
+                let uri = &destination[BIP21_SCHEME.len()..];
+                match uri.split_once('?') {
+                    Some((address, query)) => (address, Some(query)),
+                    None => (uri, None),
+                }
+            }
+            None => (destination, None),
+        };
+
+        let address = Address::from_str(address)
+            .map_err(|e| BonsaiWalletError::Generic(format!("Invalid address: {e}")))?
+            .require_network(network)
+            .map_err(|e| BonsaiWalletError::Generic(format!("Invalid address: {e}")))?;
+
+        let mut amount = None;
+        let mut label = None;
+        for param in query.unwrap_or_default().split('&') {
+            match param.split_once('=') {
+                Some(("amount", value)) => {
+                    amount = Some(Amount::from_str_in(value, Denomination::Bitcoin).map_err(
+                        |e| BonsaiWalletError::Generic(format!("Invalid BIP21 amount: {e}")),
+                    )?);
+                }
+                Some(("label", value)) => {
+                    let value = percent_decode_str(value).decode_utf8().map_err(|e| {
+                        BonsaiWalletError::Generic(format!("Invalid BIP21 label: {e}"))
+                    })?;
+                    label = Some(value.into_owned());
+                }
+                Some((key, _)) if key.starts_with("req-") => {
+                    return Err(BonsaiWalletError::Generic(format!(
+                        "Unsupported required BIP21 parameter: {key}"
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(PaymentDestination {
+            address,
+            amount,
+            label,
+        })
+    }
+}
+
+/// Input state for adding a [`Contact`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ContactForm {
+    pub(crate) name: String,
+    /// Comma-separated addresses or BIP21 URIs.
+    pub(crate) destinations: String,
+    pub(crate) notes: String,
+}
+
+impl ContactForm {
+    /// Validate the form into a [`Contact`].
+    pub(crate) fn to_contact(&self, network: Network) -> Result<Contact, BonsaiWalletError> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(BonsaiWalletError::Generic(
+                "Contact name is empty".to_string(),
+            ));
+        }
+
+        let destinations: Vec<String> = self
+            .destinations
+            .split(',')
+            .map(str::trim)
+            .filter(|destination| !destination.is_empty())
+            .map(str::to_string)
+            .collect();
+        if destinations.is_empty() {
+            return Err(BonsaiWalletError::Generic(
+                "Contact has no addresses".to_string(),
+            ));
+        }
+        for destination in &destinations {
+            PaymentDestination::parse(destination, network)?;
+        }
+
+        Ok(Contact {
+            name: name.to_string(),
+            destinations,
+            notes: self.notes.trim().to_string(),
+        })
+    }
+}
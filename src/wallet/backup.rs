@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use argon2::Argon2;
+use bitcoin::Network;
+use chacha20poly1305::ChaCha20Poly1305;
+use chacha20poly1305::Key;
+use chacha20poly1305::KeyInit;
+use chacha20poly1305::Nonce;
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::aead::AeadCore;
+use chacha20poly1305::aead::OsRng;
+use chacha20poly1305::aead::rand_core::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::settings::bonsai_settings::BonsaiSettings;
+use crate::wallet::error::BonsaiWalletError;
+use crate::wallet::wallet_data::WalletData;
+
+/// Magic bytes at the start of every backup bundle.
+const BACKUP_MAGIC: &[u8; 6] = b"BONSAI";
+/// Version of the backup bundle format.
+const BACKUP_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = BACKUP_MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+
+/// A single-file wallet backup.
+///
+/// The bundle is laid out as `MAGIC || VERSION || SALT || NONCE || CIPHERTEXT`, where the
+/// ciphertext is the TOML-encoded [`WalletBackup`] encrypted with ChaCha20Poly1305 under a
+/// key derived from the passphrase with Argon2id.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct WalletBackup {
+    pub(crate) network: Network,
+    pub(crate) wallet: WalletData,
+    pub(crate) settings: BonsaiSettings,
+}
+
+impl WalletBackup {
+    /// Default path for a backup bundle of a given [`Network`].
+    pub(crate) fn default_path(network: Network) -> PathBuf {
+        dirs::home_dir()
+            .expect("Could not find home")
+            .join(format!("bonsai-wallet-{}.backup", network))
+    }
+
+    /// Serialize and encrypt the backup with a passphrase.
+    pub(crate) fn encrypt(&self, passphrase: &str) -> Result<Vec<u8>, BonsaiWalletError> {
+        let plaintext =
+            toml::to_string(self).map_err(|e| BonsaiWalletError::Serialization(e.to_string()))?;
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = derive_key(passphrase, &salt)?;
+
+        let cipher = ChaCha20Poly1305::new(&key);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| BonsaiWalletError::Encryption(e.to_string()))?;
+
+        let mut bundle = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        bundle.extend_from_slice(BACKUP_MAGIC);
+        bundle.push(BACKUP_VERSION);
+        bundle.extend_from_slice(&salt);
+        bundle.extend_from_slice(&nonce);
+        bundle.extend_from_slice(&ciphertext);
+
+        Ok(bundle)
+    }
+
+    /// Decrypt and deserialize a backup bundle with a passphrase.
+    pub(crate) fn decrypt(bundle: &[u8], passphrase: &str) -> Result<Self, BonsaiWalletError> {
+        if bundle.len() < HEADER_LEN || !bundle.starts_with(BACKUP_MAGIC) {
+            return Err(BonsaiWalletError::Generic(
+                "Not a Bonsai backup file".to_string(),
+            ));
+        }
+
+        let version = bundle[BACKUP_MAGIC.len()];
+        if version != BACKUP_VERSION {
+            return Err(BonsaiWalletError::Generic(format!(
+                "Unsupported backup version: {version}"
+            )));
+        }
+
+        let (salt, rest) = bundle[BACKUP_MAGIC.len() + 1..].split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = derive_key(passphrase, salt)?;
+        let cipher = ChaCha20Poly1305::new(&key);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| BonsaiWalletError::Decryption)?;
+
+        let plaintext = String::from_utf8(plaintext)
+            .map_err(|e| BonsaiWalletError::Serialization(e.to_string()))?;
+        toml::from_str(&plaintext).map_err(|e| BonsaiWalletError::Serialization(e.to_string()))
+    }
+
+    /// Encrypt the backup and write it to `path`.
+    pub(crate) async fn export(
+        self,
+        path: PathBuf,
+        passphrase: String,
+    ) -> Result<PathBuf, BonsaiWalletError> {
+        let bundle = self.encrypt(&passphrase)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, bundle).await?;
+
+        Ok(path)
+    }
+
+    /// Read a backup from `path` and decrypt it.
+    pub(crate) async fn import(
+        path: PathBuf,
+        passphrase: String,
+    ) -> Result<Self, BonsaiWalletError> {
+        let bundle = tokio::fs::read(&path).await?;
+
+        Self::decrypt(&bundle, &passphrase)
+    }
+}
+
+/// Derive a ChaCha20Poly1305 [`Key`] from a passphrase using Argon2id.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, BonsaiWalletError> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| BonsaiWalletError::Encryption(e.to_string()))?;
+
+    Ok(key)
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeSet;
+
+use bdk_wallet::KeychainKind;
+use bitcoin::Amount;
+use bitcoin::FeeRate;
+use bitcoin::OutPoint;
+use bitcoin::Psbt;
+use bitcoin::SignedAmount;
+use bitcoin::Weight;
+
+use crate::node::fee_estimator::FeeEstimator;
+use crate::wallet::error::BonsaiWalletError;
+
+/// UTXOs below this value are considered for consolidation by default, in satoshis.
+pub(crate) const DEFAULT_CONSOLIDATION_THRESHOLD: u64 = 100_000;
+
+/// Weight of an input's outpoint, sequence and empty scriptSig length.
+const INPUT_BASE_WEIGHT: u64 = (32 + 4 + 4 + 1) * 4;
+
+/// Weight of a transaction's version, locktime, input and output counts and segwit marker.
+const TX_OVERHEAD_WEIGHT: u64 = (4 + 4 + 1 + 1) * 4 + 2;
+
+/// Input state for the `CONSOLIDATE` section.
+#[derive(Clone, Debug)]
+pub(crate) struct ConsolidationForm {
+    /// UTXOs below this value, in satoshis, are merged.
+    pub(crate) threshold: String,
+    pub(crate) plan: Option<ConsolidationPlan>,
+    /// The last consolidation PSBT created, base64-encoded.
+    pub(crate) psbt: Option<String>,
+    pub(crate) error: Option<BonsaiWalletError>,
+}
+
+impl Default for ConsolidationForm {
+    fn default() -> Self {
+        ConsolidationForm {
+            threshold: DEFAULT_CONSOLIDATION_THRESHOLD.to_string(),
+            plan: None,
+            psbt: None,
+            error: None,
+        }
+    }
+}
+
+/// A proposal to merge small UTXOs into a single change output.
+#[derive(Clone, Debug)]
+pub(crate) struct ConsolidationPlan {
+    pub(crate) outpoints: Vec<OutPoint>,
+    pub(crate) total: Amount,
+    /// How many distinct addresses get linked together on-chain.
+    pub(crate) addresses_linked: usize,
+    /// Feerate the consolidation is built at.
+    pub(crate) feerate: FeeRate,
+    /// Feerate the UTXOs are assumed to be spent at otherwise.
+    pub(crate) future_feerate: FeeRate,
+    /// Whether the latest blocks' feerates are in the bottom quartile of recent blocks.
+    pub(crate) fees_are_low: bool,
+    /// Fee paid by the consolidation transaction.
+    pub(crate) fee: Amount,
+    /// Fees saved on future spends, minus the consolidation fee.
+    pub(crate) savings: SignedAmount,
+}
+
+impl ConsolidationPlan {
+    /// Whether merging is worth it right now.
+    pub(crate) fn is_recommended(&self) -> bool {
+        self.fees_are_low && self.savings > SignedAmount::ZERO
+    }
+}
+
+/// Plan the consolidation of confirmed UTXOs worth less than `threshold`,
+/// priced with the feerates from the node's [`FeeEstimator`].
+pub(crate) fn plan_consolidation(
+    wallet: &bdk_wallet::Wallet,
+    fee_estimator: &FeeEstimator,
+    threshold: Amount,
+) -> Result<ConsolidationPlan, BonsaiWalletError> {
+    let (Some(feerate), Some(future_feerate)) = (fee_estimator.low(), fee_estimator.estimate())
+    else {
+        return Err(BonsaiWalletError::Generic(
+            "No feerate data yet, wait for the node to validate new blocks".to_string(),
+        ));
+    };
+
+    let utxos: Vec<_> = wallet
+        .list_unspent()
+        .filter(|utxo| utxo.chain_position.is_confirmed() && utxo.txout.value < threshold)
+        .collect();
+    if utxos.len() < 2 {
+        return Err(BonsaiWalletError::Generic(format!(
+            "Less than 2 confirmed UTXOs below {} sats",
+            threshold.to_sat()
+        )));
+    }
+
+    let input_weight = wallet
+        .public_descriptor(KeychainKind::External)
+        .max_weight_to_satisfy()
+        .map(|satisfaction| Weight::from_wu(INPUT_BASE_WEIGHT) + satisfaction)
+        .map_err(|e| BonsaiWalletError::Descriptor(e.to_string()))?;
+    let output_weight = Weight::from_wu(
+        (8 + 1
+            + wallet
+                .peek_address(KeychainKind::Internal, 0)
+                .script_pubkey()
+                .len() as u64)
+            * 4,
+    );
+    let tx_weight =
+        Weight::from_wu(TX_OVERHEAD_WEIGHT) + input_weight * utxos.len() as u64 + output_weight;
+
+    let fee = feerate.fee_wu(tx_weight).unwrap_or(Amount::MAX_MONEY);
+    // Spending the merged output costs a single input, instead of one per UTXO.
+    let future_savings = future_feerate
+        .fee_wu(input_weight * (utxos.len() as u64 - 1))
+        .unwrap_or(Amount::ZERO);
+    let savings = future_savings.to_signed().unwrap_or(SignedAmount::MAX)
+        - fee.to_signed().unwrap_or(SignedAmount::MAX);
+
+    let addresses_linked = utxos
+        .iter()
+        .map(|utxo| &utxo.txout.script_pubkey)
+        .collect::<BTreeSet<_>>()
+        .len();
+
+    Ok(ConsolidationPlan {
+        outpoints: utxos.iter().map(|utxo| utxo.outpoint).collect(),
+        total: utxos.iter().map(|utxo| utxo.txout.value).sum(),
+        addresses_linked,
+        feerate,
+        future_feerate,
+        fees_are_low: fee_estimator.fees_are_low(),
+        fee,
+        savings,
+    })
+}
+
+/// Create an unsigned [`Psbt`] spending every UTXO of a [`ConsolidationPlan`]
+/// to a single change output.
+pub(crate) fn create_consolidation_psbt(
+    wallet: &mut bdk_wallet::Wallet,
+    plan: &ConsolidationPlan,
+) -> Result<Psbt, BonsaiWalletError> {
+    let change = wallet
+        .next_unused_address(KeychainKind::Internal)
+        .script_pubkey();
+
+    let mut builder = wallet.build_tx();
+    builder
+        .add_utxos(&plan.outpoints)
+        .map_err(|e| BonsaiWalletError::Generic(format!("Failed to create PSBT: {e}")))?
+        .manually_selected_only()
+        .drain_to(change)
+        .fee_rate(plan.feerate);
+
+    builder
+        .finish()
+        .map_err(|e| BonsaiWalletError::Generic(format!("Failed to create PSBT: {e}")))
+}
+
+/// Format a [`FeeRate`] in sat/vB, with a single decimal place.
+pub(crate) fn format_feerate(feerate: FeeRate) -> String {
+    format!("{:.1} SAT/VB", feerate.to_sat_per_kwu() as f64 / 250.0)
+}
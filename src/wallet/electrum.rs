@@ -0,0 +1,780 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::RwLock as StdRwLock;
+
+use bitcoin::Amount;
+use bitcoin::Block;
+use bitcoin::Network;
+use bitcoin::OutPoint;
+use bitcoin::Script;
+use bitcoin::ScriptBuf;
+use bitcoin::Transaction;
+use bitcoin::Txid;
+use bitcoin::block::Header;
+use bitcoin::consensus::encode::deserialize_hex;
+use bitcoin::consensus::encode::serialize_hex;
+use bitcoin::constants::genesis_block;
+use bitcoin::hashes::Hash;
+use bitcoin::hashes::HashEngine;
+use bitcoin::hashes::sha256;
+use bitcoin::hashes::sha256d;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+use crate::APP_VERSION;
+use crate::node::fee_estimator::FeeEstimator;
+use crate::node::rpc::RPC_INVALID_PARAMETER;
+use crate::node::rpc::RPC_INVALID_REQUEST;
+use crate::node::rpc::RPC_METHOD_NOT_FOUND;
+use crate::node::rpc::RPC_MISC_ERROR;
+use crate::node::rpc::RPC_PARSE_ERROR;
+use crate::node::rpc::RpcError;
+use crate::node::rpc::get_header;
+use crate::node::rpc::param;
+use crate::node::rpc::required;
+use crate::node::shutdown::CancellationToken;
+use crate::node::shutdown::WeakNode;
+use crate::wallet::error::BonsaiWalletError;
+
+/// The Electrum protocol version spoken by the server.
+const PROTOCOL_VERSION: &str = "1.4";
+
+/// The most headers served by a single `blockchain.block.headers` call.
+const MAX_HEADERS: u32 = 2016;
+
+/// How many index updates can be waiting for a slow client before it misses some.
+const NOTIFICATION_CAPACITY: usize = 1000;
+
+/// A change to the [`ElectrumIndex`] that subscribed clients are notified of.
+#[derive(Clone, Debug)]
+enum Notification {
+    /// A new tip, at this height.
+    Header(u32),
+    /// The history of this script hash changed.
+    ScriptHash(String),
+}
+
+/// A wallet transaction, with what is needed to prove its inclusion in a block.
+struct IndexedTx {
+    tx: Transaction,
+    height: u32,
+    pos: usize,
+    merkle: Vec<String>,
+}
+
+#[derive(Default)]
+struct IndexData {
+    tip: Option<(u32, Header)>,
+    /// Headers of the blocks applied to the wallet.
+    headers: BTreeMap<u32, Header>,
+    transactions: HashMap<Txid, IndexedTx>,
+    /// Confirmed transactions touching each script hash, by height and position in the block.
+    histories: HashMap<String, Vec<(Txid, u32, usize)>>,
+    /// Unspent wallet outputs, with the script hash they pay to.
+    utxos: HashMap<OutPoint, (String, u32, Amount)>,
+    /// Script hashes of the wallet's revealed and lookahead scripts.
+    scripts: HashSet<String>,
+    /// Unconfirmed wallet transactions broadcast through the server, until they are mined.
+    mempool: HashMap<Txid, Transaction>,
+}
+
+impl IndexData {
+    /// The script hashes a transaction spends from or pays to.
+    fn touched(&self, tx: &Transaction) -> HashSet<String> {
+        let spent = tx
+            .input
+            .iter()
+            .filter_map(|input| self.utxos.get(&input.previous_output))
+            .map(|(script_hash, _, _)| script_hash.clone());
+        let paid = tx
+            .output
+            .iter()
+            .map(|output| script_hash(&output.script_pubkey))
+            .filter(|script_hash| self.scripts.contains(script_hash));
+
+        spent.chain(paid).collect()
+    }
+
+    /// The value of an output, if it is a wallet output or in an unconfirmed transaction.
+    fn output_value(&self, outpoint: &OutPoint) -> Option<Amount> {
+        if let Some((_, _, value)) = self.utxos.get(outpoint) {
+            return Some(*value);
+        }
+        self.mempool
+            .get(&outpoint.txid)
+            .and_then(|tx| tx.output.get(outpoint.vout as usize))
+            .map(|output| output.value)
+    }
+
+    /// The unconfirmed transactions touching a script hash, with their Electrum height and fee.
+    ///
+    /// The height is -1 for transactions spending unconfirmed outputs, and 0 otherwise.
+    /// The fee is only known if every output spent is a wallet or unconfirmed one.
+    fn mempool_history(&self, script_hash: &str) -> Vec<(Txid, i64, Option<Amount>)> {
+        let mut history: Vec<(Txid, i64, Option<Amount>)> = self
+            .mempool
+            .iter()
+            .filter(|(_, tx)| self.touched(tx).contains(script_hash))
+            .map(|(txid, tx)| {
+                let unconfirmed_parent = tx
+                    .input
+                    .iter()
+                    .any(|input| self.mempool.contains_key(&input.previous_output.txid));
+                let input_value = tx
+                    .input
+                    .iter()
+                    .map(|input| self.output_value(&input.previous_output))
+                    .sum::<Option<Amount>>();
+                let output_value: Amount = tx.output.iter().map(|output| output.value).sum();
+                let fee = input_value.and_then(|input_value| input_value.checked_sub(output_value));
+
+                (*txid, if unconfirmed_parent { -1 } else { 0 }, fee)
+            })
+            .collect();
+        // Electrum orders unconfirmed transactions by height, then by txid.
+        history.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        history
+    }
+}
+
+/// An index of the wallet's own scripts, served over the Electrum protocol.
+///
+/// Filled from the blocks applied to the wallet, so it only knows about
+/// the scripts and transactions of the loaded wallet. Its only unconfirmed
+/// transactions are the ones broadcast through the server.
+#[derive(Clone)]
+pub(crate) struct ElectrumIndex {
+    data: Arc<StdRwLock<IndexData>>,
+    notifications: broadcast::Sender<Notification>,
+}
+
+impl Default for ElectrumIndex {
+    fn default() -> Self {
+        ElectrumIndex {
+            data: Arc::default(),
+            notifications: broadcast::channel(NOTIFICATION_CAPACITY).0,
+        }
+    }
+}
+
+impl ElectrumIndex {
+    /// Record a [`Block`] that has been applied to the wallet.
+    pub(crate) fn record_block(&self, wallet: &bdk_wallet::Wallet, block: &Block, height: u32) {
+        let mut data = self.data.write().unwrap();
+
+        data.headers.insert(height, block.header);
+        let new_tip = data.tip.is_none_or(|(tip, _)| height > tip);
+        if new_tip {
+            data.tip = Some((height, block.header));
+        }
+
+        let txids: Vec<Txid> = block.txdata.iter().map(|tx| tx.compute_txid()).collect();
+        let mut changed = HashSet::new();
+        for (pos, tx) in block.txdata.iter().enumerate() {
+            let txid = txids[pos];
+            data.mempool.remove(&txid);
+            // Blocks can be applied more than once, by a rescan and by the node.
+            if data.transactions.contains_key(&txid) || wallet.get_tx(txid).is_none() {
+                continue;
+            }
+
+            for input in &tx.input {
+                if let Some((script_hash, _, _)) = data.utxos.remove(&input.previous_output) {
+                    push_history(&mut data.histories, &script_hash, txid, height, pos);
+                    changed.insert(script_hash);
+                }
+            }
+
+            for (vout, output) in tx.output.iter().enumerate() {
+                if wallet.is_mine(output.script_pubkey.clone()) {
+                    let script_hash = script_hash(&output.script_pubkey);
+                    data.utxos.insert(
+                        OutPoint::new(txid, vout as u32),
+                        (script_hash.clone(), height, output.value),
+                    );
+                    push_history(&mut data.histories, &script_hash, txid, height, pos);
+                    changed.insert(script_hash);
+                }
+            }
+
+            data.transactions.insert(
+                txid,
+                IndexedTx {
+                    tx: tx.clone(),
+                    height,
+                    pos,
+                    merkle: merkle_branch(&txids, pos),
+                },
+            );
+        }
+        drop(data);
+
+        // Nobody listening is not an error.
+        if new_tip {
+            let _ = self.notifications.send(Notification::Header(height));
+        }
+        for script_hash in changed {
+            let _ = self
+                .notifications
+                .send(Notification::ScriptHash(script_hash));
+        }
+    }
+
+    /// Replace the wallet scripts that unconfirmed transactions are matched against.
+    pub(crate) fn watch(&self, scripts: &[ScriptBuf]) {
+        self.data.write().unwrap().scripts = scripts.iter().map(|s| script_hash(s)).collect();
+    }
+
+    /// Record a transaction broadcast through the server, if it touches the wallet.
+    fn record_unconfirmed(&self, tx: Transaction) {
+        let mut data = self.data.write().unwrap();
+        let txid = tx.compute_txid();
+        if data.transactions.contains_key(&txid) {
+            return;
+        }
+
+        let changed = data.touched(&tx);
+        if changed.is_empty() {
+            return;
+        }
+        data.mempool.insert(txid, tx);
+        drop(data);
+
+        for script_hash in changed {
+            let _ = self
+                .notifications
+                .send(Notification::ScriptHash(script_hash));
+        }
+    }
+
+    /// Forget everything, for when the wallet is replaced.
+    pub(crate) fn clear(&self) {
+        *self.data.write().unwrap() = IndexData::default();
+    }
+
+    fn tip(&self) -> Option<(u32, Header)> {
+        self.data.read().unwrap().tip
+    }
+
+    fn header(&self, height: u32) -> Option<Header> {
+        self.data.read().unwrap().headers.get(&height).copied()
+    }
+
+    /// Confirmed transactions touching a script hash, then unconfirmed ones.
+    fn history(&self, script_hash: &str) -> Vec<(Txid, i64, Option<Amount>)> {
+        let data = self.data.read().unwrap();
+        let confirmed = data
+            .histories
+            .get(script_hash)
+            .into_iter()
+            .flatten()
+            .map(|(txid, height, _)| (*txid, i64::from(*height), None));
+
+        confirmed.chain(data.mempool_history(script_hash)).collect()
+    }
+
+    /// Unspent outputs paying to a script hash, with height 0 if unconfirmed.
+    ///
+    /// Outputs spent by unconfirmed transactions are left out.
+    fn unspent(&self, wanted: &str) -> Vec<(OutPoint, u32, Amount)> {
+        let data = self.data.read().unwrap();
+        let spent: HashSet<OutPoint> = data
+            .mempool
+            .values()
+            .flat_map(|tx| tx.input.iter().map(|input| input.previous_output))
+            .collect();
+
+        let confirmed = data
+            .utxos
+            .iter()
+            .filter(|(_, (hash, _, _))| hash == wanted)
+            .map(|(outpoint, (_, height, value))| (*outpoint, *height, *value));
+        let unconfirmed = data.mempool.iter().flat_map(|(txid, tx)| {
+            tx.output
+                .iter()
+                .enumerate()
+                .filter(|(_, output)| script_hash(&output.script_pubkey) == wanted)
+                .map(|(vout, output)| (OutPoint::new(*txid, vout as u32), 0, output.value))
+        });
+
+        confirmed
+            .chain(unconfirmed)
+            .filter(|(outpoint, _, _)| !spent.contains(outpoint))
+            .collect()
+    }
+
+    /// The confirmed balance of a script hash, and the change unconfirmed transactions make to it.
+    fn balance(&self, wanted: &str) -> (Amount, i64) {
+        let data = self.data.read().unwrap();
+        let confirmed: Amount = data
+            .utxos
+            .values()
+            .filter(|(hash, _, _)| hash == wanted)
+            .map(|(_, _, value)| *value)
+            .sum();
+
+        let mut unconfirmed = 0;
+        for tx in data.mempool.values() {
+            for input in &tx.input {
+                if let Some((hash, _, value)) = data.utxos.get(&input.previous_output)
+                    && hash == wanted
+                {
+                    unconfirmed -= value.to_sat() as i64;
+                }
+            }
+            for output in &tx.output {
+                if script_hash(&output.script_pubkey) == wanted {
+                    unconfirmed += output.value.to_sat() as i64;
+                }
+            }
+        }
+
+        (confirmed, unconfirmed)
+    }
+
+    fn transaction(&self, txid: &Txid) -> Option<Transaction> {
+        let data = self.data.read().unwrap();
+        data.transactions
+            .get(txid)
+            .map(|indexed| indexed.tx.clone())
+            .or_else(|| data.mempool.get(txid).cloned())
+    }
+
+    fn merkle(&self, txid: &Txid) -> Option<(u32, usize, Vec<String>)> {
+        let data = self.data.read().unwrap();
+        data.transactions
+            .get(txid)
+            .map(|indexed| (indexed.height, indexed.pos, indexed.merkle.clone()))
+    }
+
+    /// The Electrum status of a script hash: the hash of its history, or `None` if empty.
+    fn status(&self, script_hash: &str) -> Option<String> {
+        let history = self.history(script_hash);
+        if history.is_empty() {
+            return None;
+        }
+
+        let preimage: String = history
+            .iter()
+            .map(|(txid, height, _)| format!("{txid}:{height}:"))
+            .collect();
+        Some(hex::encode(
+            sha256::Hash::hash(preimage.as_bytes()).to_byte_array(),
+        ))
+    }
+}
+
+/// Add a confirmed transaction to a script hash's history, in the order
+/// the Electrum status hash expects: by height, then by position in the block.
+fn push_history(
+    histories: &mut HashMap<String, Vec<(Txid, u32, usize)>>,
+    script_hash: &str,
+    txid: Txid,
+    height: u32,
+    pos: usize,
+) {
+    let history = histories.entry(script_hash.to_string()).or_default();
+    if !history.iter().any(|(seen, _, _)| *seen == txid) {
+        history.push((txid, height, pos));
+        history.sort_by_key(|(_, height, pos)| (*height, *pos));
+    }
+}
+
+/// The Electrum script hash of a script: its reversed SHA256, in hex.
+fn script_hash(script: &Script) -> String {
+    let mut hash = sha256::Hash::hash(script.as_bytes()).to_byte_array();
+    hash.reverse();
+    hex::encode(hash)
+}
+
+/// The merkle branch of the transaction at `pos`, from the leaves up.
+fn merkle_branch(txids: &[Txid], mut pos: usize) -> Vec<String> {
+    let mut level: Vec<sha256d::Hash> = txids.iter().map(|txid| txid.to_raw_hash()).collect();
+    let mut branch = Vec::new();
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        branch.push(level[pos ^ 1].to_string());
+
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut engine = sha256d::Hash::engine();
+                engine.input(pair[0].as_byte_array());
+                engine.input(pair[1].as_byte_array());
+                sha256d::Hash::from_engine(engine)
+            })
+            .collect();
+        pos /= 2;
+    }
+
+    branch
+}
+
+/// Serve the Electrum protocol for the wallet's own scripts, on `address`.
+///
+/// Runs until the token is cancelled, along with every client session.
+pub(crate) async fn serve(
+    node: WeakNode,
+    index: ElectrumIndex,
+    fee_estimator: FeeEstimator,
+    network: Network,
+    address: SocketAddr,
+    token: CancellationToken,
+) -> Result<(), BonsaiWalletError> {
+    let listener = TcpListener::bind(address).await?;
+    info!("Electrum server listening on {address}");
+
+    token
+        .clone()
+        .run(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        warn!("Failed to accept Electrum client: {e}");
+                        continue;
+                    }
+                };
+                debug!("Electrum client connected from {peer}");
+
+                let session = Session {
+                    node: node.clone(),
+                    index: index.clone(),
+                    fee_estimator: fee_estimator.clone(),
+                    network,
+                    script_hashes: HashSet::new(),
+                    headers: false,
+                };
+                tokio::spawn(token.clone().run(session.run(stream)));
+            }
+        })
+        .await;
+
+    info!("Electrum server stopped");
+    Ok(())
+}
+
+/// A connected client and its subscriptions.
+struct Session {
+    node: WeakNode,
+    index: ElectrumIndex,
+    fee_estimator: FeeEstimator,
+    network: Network,
+    script_hashes: HashSet<String>,
+    headers: bool,
+}
+
+impl Session {
+    async fn run(mut self, stream: TcpStream) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut notifications = self.index.notifications.subscribe();
+
+        loop {
+            let message = tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => Some(self.handle_line(&line).await),
+                    _ => break,
+                },
+                notification = notifications.recv() => match notification {
+                    Ok(notification) => self.notification(notification).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Electrum client missed {missed} notifications");
+                        None
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            };
+
+            if let Some(message) = message
+                && write_message(&mut writer, &message).await.is_err()
+            {
+                break;
+            }
+        }
+
+        debug!("Electrum client disconnected");
+    }
+
+    async fn handle_line(&mut self, line: &str) -> Value {
+        match serde_json::from_str::<Value>(line) {
+            Ok(Value::Array(calls)) => {
+                let mut responses = Vec::with_capacity(calls.len());
+                for call in calls {
+                    responses.push(self.handle_call(call).await);
+                }
+                Value::Array(responses)
+            }
+            Ok(call) => self.handle_call(call).await,
+            Err(e) => response(
+                Value::Null,
+                Err(RpcError::new(RPC_PARSE_ERROR, format!("Parse error: {e}"))),
+            ),
+        }
+    }
+
+    async fn handle_call(&mut self, call: Value) -> Value {
+        let id = call.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = call.get("method").and_then(Value::as_str) else {
+            return response(
+                id,
+                Err(RpcError::new(RPC_INVALID_REQUEST, "Missing method")),
+            );
+        };
+        let params = call.get("params").cloned().unwrap_or(Value::Null);
+
+        debug!("Electrum call to {method}");
+        response(id, self.dispatch(method, &params).await)
+    }
+
+    async fn dispatch(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "server.version" => Ok(json!([format!("Bonsai {APP_VERSION}"), PROTOCOL_VERSION])),
+            "server.banner" => Ok(json!("Bonsai personal Electrum server")),
+            "server.donation_address" => Ok(json!("")),
+            "server.peers.subscribe" => Ok(json!([])),
+            "server.ping" => Ok(Value::Null),
+            "server.features" => Ok(json!({
+                "genesis_hash": genesis_block(self.network).block_hash().to_string(),
+                "hosts": {},
+                "protocol_min": PROTOCOL_VERSION,
+                "protocol_max": PROTOCOL_VERSION,
+                "pruning": null,
+                "server_version": format!("Bonsai {APP_VERSION}"),
+                "hash_function": "sha256",
+            })),
+
+            "blockchain.headers.subscribe" => {
+                self.headers = true;
+                let (height, header) = self.tip().await?;
+                Ok(json!({ "height": height, "hex": serialize_hex(&header) }))
+            }
+            "blockchain.block.header" => {
+                let height: u32 = required(params, 0, "height")?;
+                if param::<u32>(params, 1, "cp_height")?.unwrap_or(0) != 0 {
+                    return Err(RpcError::new(
+                        RPC_INVALID_PARAMETER,
+                        "Checkpoint proofs are not supported",
+                    ));
+                }
+                Ok(json!(serialize_hex(&self.header(height).await?)))
+            }
+            "blockchain.block.headers" => {
+                let start: u32 = required(params, 0, "start_height")?;
+                let count: u32 = required(params, 1, "count")?;
+                let (tip, _) = self.tip().await?;
+
+                let end = start.saturating_add(count.min(MAX_HEADERS)).min(tip + 1);
+                let mut hex = String::new();
+                for height in start..end {
+                    hex.push_str(&serialize_hex(&self.header(height).await?));
+                }
+                Ok(json!({
+                    "count": end.saturating_sub(start),
+                    "hex": hex,
+                    "max": MAX_HEADERS,
+                }))
+            }
+            "blockchain.estimatefee" => Ok(match self.fee_estimator.estimate() {
+                // sat/kwu to BTC/kvB.
+                Some(feerate) => json!(feerate.to_sat_per_kwu() as f64 * 4.0 / 100_000_000.0),
+                None => json!(-1),
+            }),
+            "blockchain.relayfee" => Ok(json!(0.00001)),
+
+            "blockchain.scripthash.get_balance" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                let (confirmed, unconfirmed) = self.index.balance(&script_hash);
+                Ok(json!({ "confirmed": confirmed.to_sat(), "unconfirmed": unconfirmed }))
+            }
+            "blockchain.scripthash.get_history" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                let history: Vec<Value> = self
+                    .index
+                    .history(&script_hash)
+                    .iter()
+                    .map(|(txid, height, fee)| history_json(txid, *height, *fee))
+                    .collect();
+                Ok(Value::Array(history))
+            }
+            "blockchain.scripthash.get_mempool" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                let mempool: Vec<Value> = self
+                    .index
+                    .history(&script_hash)
+                    .iter()
+                    .filter(|(_, height, _)| *height <= 0)
+                    .map(|(txid, height, fee)| history_json(txid, *height, *fee))
+                    .collect();
+                Ok(Value::Array(mempool))
+            }
+            "blockchain.scripthash.listunspent" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                let unspent: Vec<Value> = self
+                    .index
+                    .unspent(&script_hash)
+                    .iter()
+                    .map(|(outpoint, height, value)| {
+                        json!({
+                            "tx_hash": outpoint.txid.to_string(),
+                            "tx_pos": outpoint.vout,
+                            "height": height,
+                            "value": value.to_sat(),
+                        })
+                    })
+                    .collect();
+                Ok(Value::Array(unspent))
+            }
+            "blockchain.scripthash.subscribe" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                let status = self.index.status(&script_hash);
+                self.script_hashes.insert(script_hash);
+                Ok(json!(status))
+            }
+            "blockchain.scripthash.unsubscribe" => {
+                let script_hash: String = required(params, 0, "scripthash")?;
+                Ok(json!(self.script_hashes.remove(&script_hash)))
+            }
+
+            "blockchain.transaction.get" => {
+                let txid = parse_txid(&required::<String>(params, 0, "tx_hash")?)?;
+                if param::<bool>(params, 1, "verbose")?.unwrap_or(false) {
+                    return Err(RpcError::new(
+                        RPC_INVALID_PARAMETER,
+                        "Verbose transactions are not supported",
+                    ));
+                }
+                let tx = self.index.transaction(&txid).ok_or_else(|| {
+                    RpcError::new(RPC_MISC_ERROR, format!("Transaction {txid} not found"))
+                })?;
+                Ok(json!(serialize_hex(&tx)))
+            }
+            "blockchain.transaction.get_merkle" => {
+                let txid = parse_txid(&required::<String>(params, 0, "tx_hash")?)?;
+                let (height, pos, merkle) = self.index.merkle(&txid).ok_or_else(|| {
+                    RpcError::new(RPC_MISC_ERROR, format!("Transaction {txid} not found"))
+                })?;
+                Ok(json!({ "block_height": height, "merkle": merkle, "pos": pos }))
+            }
+            "blockchain.transaction.broadcast" => {
+                let hex: String = required(params, 0, "raw_tx")?;
+                let tx: Transaction = deserialize_hex(&hex).map_err(|e| {
+                    RpcError::new(RPC_INVALID_PARAMETER, format!("TX decode failed: {e}"))
+                })?;
+                let txid = tx.compute_txid();
+
+                let handle = self.node.upgrade()?;
+                let node = handle.read().await;
+                node.broadcast_transaction(tx.clone())
+                    .await
+                    .map_err(RpcError::misc)?;
+                info!("Broadcast transaction {txid} received over Electrum");
+                self.index.record_unconfirmed(tx);
+
+                Ok(json!(txid.to_string()))
+            }
+            "mempool.get_fee_histogram" => Err(RpcError::new(
+                RPC_METHOD_NOT_FOUND,
+                "Fee histograms are not supported, since the node keeps no mempool",
+            )),
+
+            _ => Err(RpcError::new(RPC_METHOD_NOT_FOUND, "Method not found")),
+        }
+    }
+
+    /// The notification for a change to the index, if the client subscribed to it.
+    async fn notification(&self, notification: Notification) -> Option<Value> {
+        match notification {
+            Notification::Header(height) if self.headers => {
+                // A rescan replays old blocks, which are no new tip to clients.
+                let (tip, _) = self.tip().await.ok()?;
+                if height < tip {
+                    return None;
+                }
+                let header = self.header(height).await.ok()?;
+                Some(json!({
+                    "jsonrpc": "2.0",
+                    "method": "blockchain.headers.subscribe",
+                    "params": [{ "height": height, "hex": serialize_hex(&header) }],
+                }))
+            }
+            Notification::ScriptHash(script_hash) if self.script_hashes.contains(&script_hash) => {
+                let status = self.index.status(&script_hash);
+                Some(json!({
+                    "jsonrpc": "2.0",
+                    "method": "blockchain.scripthash.subscribe",
+                    "params": [script_hash, status],
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    /// The node's validated tip, or the index's if the node is not running.
+    async fn tip(&self) -> Result<(u32, Header), RpcError> {
+        let handle = match self.node.upgrade() {
+            Ok(handle) => handle,
+            Err(e) => return self.index.tip().ok_or_else(|| e.into()),
+        };
+
+        let node = handle.read().await;
+        let height = node.get_validation_height().map_err(RpcError::misc)?;
+        Ok((height, get_header(&node, height)?))
+    }
+
+    /// A header from the index, or from the node's header chain if the wallet never saw its block.
+    async fn header(&self, height: u32) -> Result<Header, RpcError> {
+        if let Some(header) = self.index.header(height) {
+            return Ok(header);
+        }
+
+        let handle = self.node.upgrade()?;
+        let node = handle.read().await;
+        get_header(&node, height)
+    }
+}
+
+/// A history entry, with the fee of unconfirmed transactions if it is known.
+fn history_json(txid: &Txid, height: i64, fee: Option<Amount>) -> Value {
+    let mut entry = json!({ "tx_hash": txid.to_string(), "height": height });
+    if let Some(fee) = fee {
+        entry["fee"] = json!(fee.to_sat());
+    }
+    entry
+}
+
+fn parse_txid(txid: &str) -> Result<Txid, RpcError> {
+    txid.parse()
+        .map_err(|_| RpcError::new(RPC_INVALID_PARAMETER, "Invalid tx_hash"))
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+async fn write_message(writer: &mut OwnedWriteHalf, message: &Value) -> std::io::Result<()> {
+    let mut line = message.to_string();
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub(crate) enum BonsaiWalletError {
+    #[error("Generic Error: {0}")]
+    Generic(String),
+
+    #[error("I/O Error: {0}")]
+    Io(String),
+
+    #[error("Serialization Error: {0}")]
+    Serialization(String),
+
+    #[error("Descriptor Error: {0}")]
+    Descriptor(String),
+
+    #[error("Chain Error: {0}")]
+    Chain(String),
+
+    #[error("Encryption Error: {0}")]
+    Encryption(String),
+
+    #[error("Decryption Error: wrong passphrase or corrupted backup")]
+    Decryption,
+}
+
+impl From<std::io::Error> for BonsaiWalletError {
+    fn from(e: std::io::Error) -> Self {
+        BonsaiWalletError::Io(e.to_string())
+    }
+}
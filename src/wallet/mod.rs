@@ -1 +1,11 @@
+pub(crate) mod activity;
+pub(crate) mod address_book;
+pub(crate) mod backup;
+pub(crate) mod consolidation;
+pub(crate) mod electrum;
+pub(crate) mod error;
 pub(crate) mod placeholder;
+pub(crate) mod rescan;
+pub(crate) mod send;
+pub(crate) mod view;
+pub(crate) mod wallet_data;
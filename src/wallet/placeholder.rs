@@ -1,24 +1,723 @@
+use core::fmt::Debug;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bdk_floresta::Node;
+use bdk_wallet::Balance;
+use bdk_wallet::Update;
+use bdk_wallet::chain::BlockId;
+use bitcoin::Address;
+use bitcoin::Amount;
+use bitcoin::Block;
+use bitcoin::Network;
+use bitcoin::ScriptBuf;
 use iced::Element;
-use iced::widget::column;
-use iced::widget::text;
+use iced::Task;
+use iced::clipboard;
+use iced::task::Handle;
+use tokio::sync::RwLock;
+use tokio::sync::watch;
+use tracing::error;
+use tracing::info;
+
+use crate::node::fee_estimator::FeeEstimator;
+use crate::node::shutdown::CancellationToken;
+use crate::settings::bonsai_settings::BonsaiSettings;
+use crate::wallet::activity::MAX_ACTIVITY;
+use crate::wallet::activity::WalletEvent;
+use crate::wallet::activity::wallet_events;
+use crate::wallet::address_book::Contact;
+use crate::wallet::address_book::ContactForm;
+use crate::wallet::address_book::PaymentDestination;
+use crate::wallet::backup::WalletBackup;
+use crate::wallet::consolidation::ConsolidationForm;
+use crate::wallet::consolidation::create_consolidation_psbt;
+use crate::wallet::consolidation::plan_consolidation;
+use crate::wallet::electrum::ElectrumIndex;
+use crate::wallet::error::BonsaiWalletError;
+use crate::wallet::rescan::rescan;
+use crate::wallet::send::SendForm;
+use crate::wallet::send::create_psbt;
+use crate::wallet::send::paid_before;
+use crate::wallet::wallet_data::WalletData;
+
+pub struct Wallet {
+    pub(crate) network: Network,
+    /// The active [`bdk_wallet::Wallet`], if one is loaded.
+    pub(crate) inner: Option<bdk_wallet::Wallet>,
+    /// Descriptors, birthday and metadata of the active wallet.
+    pub(crate) data: Option<WalletData>,
+    /// Height from which a rescan has to be started, once the node is running.
+    pub(crate) pending_rescan: Option<u32>,
+    /// Height of the last block applied by an ongoing rescan.
+    pub(crate) rescan_height: Option<u32>,
+    /// Aborts the ongoing rescan, if a block it streams can't be applied.
+    rescan_handle: Option<Handle>,
+    /// Why the last rescan failed, if it did.
+    pub(crate) rescan_error: Option<BonsaiWalletError>,
+    /// The wallet's scripts, republished after every applied block for the rescan to match.
+    scripts: watch::Sender<Vec<ScriptBuf>>,
+    /// Wallet transactions seen in blocks, newest first.
+    pub(crate) activity: VecDeque<WalletEvent>,
+    /// The wallet's scripts and transactions, served by the Electrum server.
+    pub(crate) electrum_index: ElectrumIndex,
+
+    pub(crate) backup_path_input: String,
+    pub(crate) backup_passphrase_input: String,
+    pub(crate) restore_path_input: String,
+    pub(crate) restore_passphrase_input: String,
+    pub(crate) backup_status: Option<Result<String, BonsaiWalletError>>,
+
+    pub(crate) contact_form: ContactForm,
+    pub(crate) contact_error: Option<BonsaiWalletError>,
+    pub(crate) send_form: SendForm,
+    pub(crate) consolidation_form: ConsolidationForm,
+}
+
+impl Default for Wallet {
+    fn default() -> Self {
+        Wallet {
+            network: Network::Signet,
+            inner: None,
+            data: None,
+            pending_rescan: None,
+            rescan_height: None,
+            rescan_handle: None,
+            rescan_error: None,
+            scripts: watch::Sender::new(Vec::new()),
+            activity: VecDeque::new(),
+            electrum_index: ElectrumIndex::default(),
+            backup_path_input: String::new(),
+            backup_passphrase_input: String::new(),
+            restore_path_input: String::new(),
+            restore_passphrase_input: String::new(),
+            backup_status: None,
+            contact_form: ContactForm::default(),
+            contact_error: None,
+            send_form: SendForm::default(),
+            consolidation_form: ConsolidationForm::default(),
+        }
+    }
+}
 
-#[derive(Default)]
-pub struct Wallet {}
+#[derive(Clone)]
+pub enum WalletMessage {
+    BackupPathInputChanged(String),
+    BackupPassphraseInputChanged(String),
+    ExportBackup,
+    BackupExported(Result<PathBuf, BonsaiWalletError>),
+    RestorePathInputChanged(String),
+    RestorePassphraseInputChanged(String),
+    RestoreBackup,
+    BackupRestored(Result<Box<WalletBackup>, BonsaiWalletError>),
+    /// The settings bundled in a backup whose wallet was restored, for the given network.
+    SettingsRestored(Network, Box<BonsaiSettings>),
+    RescanBlock(u32, Box<Block>),
+    RescanComplete(BlockId),
+    RescanFailed(BonsaiWalletError),
+    RescanCancelled,
+    ContactNameInputChanged(String),
+    ContactDestinationsInputChanged(String),
+    ContactNotesInputChanged(String),
+    AddContact,
+    RemoveContact(usize),
+    PayTo(String),
+    SendRecipientInputChanged(String),
+    SendAmountInputChanged(String),
+    CreatePsbt,
+    CopyPsbt,
+    ConsolidationThresholdInputChanged(String),
+    PlanConsolidation,
+    CreateConsolidationPsbt,
+    CopyConsolidationPsbt,
+}
 
-#[derive(Debug, Clone)]
-pub enum WalletMessage {}
+// Passphrases must never end up in the logs.
+impl Debug for WalletMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BackupPathInputChanged(path) => write!(f, "BackupPathInputChanged({path})"),
+            Self::BackupPassphraseInputChanged(_) => write!(f, "BackupPassphraseInputChanged"),
+            Self::ExportBackup => write!(f, "ExportBackup"),
+            Self::BackupExported(result) => match result {
+                Ok(path) => write!(f, "BackupExported({})", path.to_string_lossy()),
+                Err(e) => write!(f, "BackupExported({e})"),
+            },
+            Self::RestorePathInputChanged(path) => write!(f, "RestorePathInputChanged({path})"),
+            Self::RestorePassphraseInputChanged(_) => write!(f, "RestorePassphraseInputChanged"),
+            Self::RestoreBackup => write!(f, "RestoreBackup"),
+            Self::BackupRestored(result) => match result {
+                Ok(backup) => write!(f, "BackupRestored({})", backup.network),
+                Err(e) => write!(f, "BackupRestored({e})"),
+            },
+            Self::SettingsRestored(network, _) => write!(f, "SettingsRestored({network})"),
+            Self::RescanBlock(height, _) => write!(f, "RescanBlock({height})"),
+            Self::RescanComplete(tip) => write!(f, "RescanComplete({})", tip.height),
+            Self::RescanFailed(e) => write!(f, "RescanFailed({e})"),
+            Self::RescanCancelled => write!(f, "RescanCancelled"),
+            Self::ContactNameInputChanged(name) => write!(f, "ContactNameInputChanged({name})"),
+            Self::ContactDestinationsInputChanged(destinations) => {
+                write!(f, "ContactDestinationsInputChanged({destinations})")
+            }
+            Self::ContactNotesInputChanged(_) => write!(f, "ContactNotesInputChanged"),
+            Self::AddContact => write!(f, "AddContact"),
+            Self::RemoveContact(idx) => write!(f, "RemoveContact({idx})"),
+            Self::PayTo(destination) => write!(f, "PayTo({destination})"),
+            Self::SendRecipientInputChanged(recipient) => {
+                write!(f, "SendRecipientInputChanged({recipient})")
+            }
+            Self::SendAmountInputChanged(amount) => write!(f, "SendAmountInputChanged({amount})"),
+            Self::CreatePsbt => write!(f, "CreatePsbt"),
+            Self::CopyPsbt => write!(f, "CopyPsbt"),
+            Self::ConsolidationThresholdInputChanged(threshold) => {
+                write!(f, "ConsolidationThresholdInputChanged({threshold})")
+            }
+            Self::PlanConsolidation => write!(f, "PlanConsolidation"),
+            Self::CreateConsolidationPsbt => write!(f, "CreateConsolidationPsbt"),
+            Self::CopyConsolidationPsbt => write!(f, "CopyConsolidationPsbt"),
+        }
+    }
+}
 
 impl Wallet {
-    pub fn update(&mut self, message: WalletMessage) {
-        match message {}
+    /// Load the wallet persisted for a given [`Network`], if any.
+    ///
+    /// Since chain data is not persisted, a rescan from the
+    /// wallet's birthday is scheduled for when the node starts.
+    pub(crate) fn load(network: Network) -> Self {
+        let backup_path = WalletBackup::default_path(network)
+            .to_string_lossy()
+            .to_string();
+        let mut wallet = Wallet {
+            network,
+            backup_path_input: backup_path.clone(),
+            restore_path_input: backup_path,
+            ..Wallet::default()
+        };
+
+        if let Some(data) = WalletData::load(network) {
+            match data.create_wallet(network) {
+                Ok(inner) => {
+                    wallet.inner = Some(inner);
+                    wallet.pending_rescan = Some(data.birthday);
+                    wallet.data = Some(data);
+                }
+                Err(e) => error!("Failed to load wallet: {e}"),
+            }
+        }
+        wallet.publish_scripts();
+
+        wallet
+    }
+
+    pub fn update(&mut self, message: WalletMessage) -> Task<WalletMessage> {
+        match message {
+            WalletMessage::BackupPathInputChanged(path) => {
+                self.backup_path_input = path;
+                Task::none()
+            }
+            WalletMessage::BackupPassphraseInputChanged(passphrase) => {
+                self.backup_passphrase_input = passphrase;
+                Task::none()
+            }
+            // Needs the application settings, so it is handled by [`Wallet::export_backup`].
+            WalletMessage::ExportBackup => Task::none(),
+            WalletMessage::BackupExported(result) => {
+                self.backup_passphrase_input.clear();
+                self.backup_status = Some(result.map(|path| {
+                    info!("Exported wallet backup to {}", path.to_string_lossy());
+                    format!("BACKUP EXPORTED TO {}", path.to_string_lossy())
+                }));
+                Task::none()
+            }
+            WalletMessage::RestorePathInputChanged(path) => {
+                self.restore_path_input = path;
+                Task::none()
+            }
+            WalletMessage::RestorePassphraseInputChanged(passphrase) => {
+                self.restore_passphrase_input = passphrase;
+                Task::none()
+            }
+            WalletMessage::RestoreBackup => {
+                let path = PathBuf::from(&self.restore_path_input);
+                let passphrase = std::mem::take(&mut self.restore_passphrase_input);
+
+                Task::perform(WalletBackup::import(path, passphrase), |result| {
+                    WalletMessage::BackupRestored(result.map(Box::new))
+                })
+            }
+            WalletMessage::BackupRestored(result) => {
+                match result.and_then(|backup| self.restore(*backup)) {
+                    Ok((network, settings)) => {
+                        self.backup_status =
+                            Some(Ok("BACKUP RESTORED, RESCANNING FROM BIRTHDAY".to_string()));
+                        Task::done(WalletMessage::SettingsRestored(network, Box::new(settings)))
+                    }
+                    Err(e) => {
+                        error!("Failed to restore wallet backup: {e}");
+                        self.backup_status = Some(Err(e));
+                        Task::none()
+                    }
+                }
+            }
+            // Applied to the application settings by `Bonsai`, which owns them.
+            WalletMessage::SettingsRestored(..) => Task::none(),
+            WalletMessage::RescanBlock(height, block) => {
+                if let Err(e) = self.apply_block(&block, height) {
+                    return Task::done(WalletMessage::RescanFailed(e));
+                }
+                self.rescan_height = Some(height);
+                Task::none()
+            }
+            WalletMessage::RescanComplete(tip) => {
+                self.rescan_height = None;
+                self.rescan_handle = None;
+                if let Err(e) = self.connect_tip(tip) {
+                    error!("Wallet rescan failed: {e}");
+                    self.rescan_error = Some(e);
+                }
+                Task::none()
+            }
+            WalletMessage::RescanFailed(e) => {
+                error!("Wallet rescan failed: {e}");
+                // The rescan stops on its own when the node fails, but not when the wallet does.
+                if let Some(handle) = self.rescan_handle.take() {
+                    handle.abort();
+                }
+                self.rescan_height = None;
+                self.rescan_error = Some(e);
+                Task::none()
+            }
+            WalletMessage::RescanCancelled => {
+                // Resume from where it stopped, once the node runs again.
+                self.pending_rescan = self.rescan_height.take();
+                Task::none()
+            }
+            WalletMessage::ContactNameInputChanged(name) => {
+                self.contact_form.name = name;
+                Task::none()
+            }
+            WalletMessage::ContactDestinationsInputChanged(destinations) => {
+                self.contact_form.destinations = destinations;
+                Task::none()
+            }
+            WalletMessage::ContactNotesInputChanged(notes) => {
+                self.contact_form.notes = notes;
+                Task::none()
+            }
+            WalletMessage::AddContact => {
+                let Some(data) = self.data.as_mut() else {
+                    self.contact_error =
+                        Some(BonsaiWalletError::Generic("No wallet loaded".to_string()));
+                    return Task::none();
+                };
+                match self.contact_form.to_contact(self.network) {
+                    Ok(contact) => {
+                        data.address_book.push(contact);
+                        self.persist();
+                        self.contact_form = ContactForm::default();
+                        self.contact_error = None;
+                    }
+                    Err(e) => self.contact_error = Some(e),
+                }
+                Task::none()
+            }
+            WalletMessage::RemoveContact(idx) => {
+                if let Some(data) = self.data.as_mut()
+                    && idx < data.address_book.len()
+                {
+                    data.address_book.remove(idx);
+                    self.persist();
+                }
+                Task::none()
+            }
+            WalletMessage::PayTo(destination) => {
+                if let Ok(parsed) = PaymentDestination::parse(&destination, self.network)
+                    && let Some(amount) = parsed.amount
+                {
+                    self.send_form.amount = amount.to_sat().to_string();
+                }
+                self.send_form.recipient = destination;
+                self.send_form.psbt = None;
+                self.send_form.error = None;
+                self.send_form.reuse_warning = self.address_reuse_warning();
+                Task::none()
+            }
+            WalletMessage::SendRecipientInputChanged(recipient) => {
+                self.send_form.recipient = recipient;
+                self.send_form.psbt = None;
+                self.send_form.reuse_warning = self.address_reuse_warning();
+                Task::none()
+            }
+            WalletMessage::SendAmountInputChanged(amount) => {
+                if amount.chars().all(|c| c.is_ascii_digit()) {
+                    self.send_form.amount = amount;
+                    self.send_form.psbt = None;
+                }
+                Task::none()
+            }
+            WalletMessage::CreatePsbt => {
+                let result = self.create_send_psbt();
+                match result {
+                    Ok(psbt) => {
+                        self.send_form.psbt = Some(psbt);
+                        self.send_form.error = None;
+                    }
+                    Err(e) => {
+                        self.send_form.psbt = None;
+                        self.send_form.error = Some(e);
+                    }
+                }
+                Task::none()
+            }
+            WalletMessage::CopyPsbt => match &self.send_form.psbt {
+                Some(psbt) => clipboard::write(psbt.clone()),
+                None => Task::none(),
+            },
+            WalletMessage::ConsolidationThresholdInputChanged(threshold) => {
+                if threshold.chars().all(|c| c.is_ascii_digit()) {
+                    self.consolidation_form.threshold = threshold;
+                    self.consolidation_form.plan = None;
+                    self.consolidation_form.psbt = None;
+                }
+                Task::none()
+            }
+            // Needs the node's feerates, so it is handled by [`Wallet::plan_consolidation`].
+            WalletMessage::PlanConsolidation => Task::none(),
+            WalletMessage::CreateConsolidationPsbt => {
+                let result = match (self.inner.as_mut(), &self.consolidation_form.plan) {
+                    (Some(wallet), Some(plan)) => create_consolidation_psbt(wallet, plan),
+                    _ => Err(BonsaiWalletError::Generic(
+                        "No consolidation planned".to_string(),
+                    )),
+                };
+                match result {
+                    Ok(psbt) => {
+                        self.consolidation_form.psbt = Some(psbt.to_string());
+                        self.consolidation_form.error = None;
+                    }
+                    Err(e) => {
+                        self.consolidation_form.psbt = None;
+                        self.consolidation_form.error = Some(e);
+                    }
+                }
+                Task::none()
+            }
+            WalletMessage::CopyConsolidationPsbt => match &self.consolidation_form.psbt {
+                Some(psbt) => clipboard::write(psbt.clone()),
+                None => Task::none(),
+            },
+        }
+    }
+
+    /// Write the active wallet's [`WalletData`] to disk.
+    fn persist(&self) {
+        if let Some(data) = &self.data
+            && let Err(e) = data.save(self.network)
+        {
+            error!("Failed to save wallet: {e}");
+        }
+    }
+
+    /// Create a base64-encoded PSBT from the `SEND` section inputs.
+    fn create_send_psbt(&mut self) -> Result<String, BonsaiWalletError> {
+        let destination = PaymentDestination::parse(&self.send_form.recipient, self.network)?;
+        let amount = self
+            .send_form
+            .amount
+            .parse::<u64>()
+            .map(Amount::from_sat)
+            .map_err(|e| BonsaiWalletError::Generic(format!("Invalid amount: {e}")))?;
+
+        let Some(wallet) = self.inner.as_mut() else {
+            return Err(BonsaiWalletError::Generic("No wallet loaded".to_string()));
+        };
+        let psbt = create_psbt(wallet, &destination, amount)?;
+
+        // Keep the payee's BIP21 label, or contact name, to recognize the address later.
+        let label = destination.label.clone().or_else(|| {
+            self.contact_paid_at(&destination.address)
+                .map(|contact| contact.name.clone())
+        });
+        if let Some(label) = label
+            && let Some(data) = self.data.as_mut()
+        {
+            data.labels.insert(destination.address.to_string(), label);
+            self.persist();
+        }
+
+        Ok(psbt.to_string())
+    }
+
+    /// The label of the `SEND` recipient, from its BIP21 URI or the wallet's labels.
+    pub(crate) fn recipient_label(&self) -> Option<String> {
+        let destination =
+            PaymentDestination::parse(&self.send_form.recipient, self.network).ok()?;
+        destination.label.or_else(|| {
+            self.data
+                .as_ref()?
+                .labels
+                .get(&destination.address.to_string())
+                .cloned()
+        })
+    }
+
+    /// Plan the consolidation of the active wallet's small UTXOs
+    /// against the feerates seen by the embedded node.
+    pub(crate) fn plan_consolidation(&mut self, fee_estimator: &FeeEstimator) {
+        let result = match (
+            self.inner.as_ref(),
+            self.consolidation_form.threshold.parse::<u64>(),
+        ) {
+            (Some(wallet), Ok(threshold)) => {
+                plan_consolidation(wallet, fee_estimator, Amount::from_sat(threshold))
+            }
+            (None, _) => Err(BonsaiWalletError::Generic("No wallet loaded".to_string())),
+            (_, Err(e)) => Err(BonsaiWalletError::Generic(format!(
+                "Invalid threshold: {e}"
+            ))),
+        };
+
+        self.consolidation_form.psbt = None;
+        match result {
+            Ok(plan) => {
+                self.consolidation_form.plan = Some(plan);
+                self.consolidation_form.error = None;
+            }
+            Err(e) => {
+                self.consolidation_form.plan = None;
+                self.consolidation_form.error = Some(e);
+            }
+        }
+    }
+
+    /// The contacts in the active wallet's address book.
+    pub(crate) fn contacts(&self) -> &[Contact] {
+        self.data
+            .as_ref()
+            .map(|data| data.address_book.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The contact that can be paid at `address`, if any.
+    fn contact_paid_at(&self, address: &Address) -> Option<&Contact> {
+        self.contacts().iter().find(|contact| {
+            contact.destinations.iter().any(|d| {
+                PaymentDestination::parse(d, self.network)
+                    .is_ok_and(|parsed| parsed.address == *address)
+            })
+        })
+    }
+
+    /// A warning if the `SEND` recipient has already been paid by this wallet.
+    ///
+    /// Walks every wallet transaction, so it is kept in [`SendForm`] and
+    /// only recomputed when the recipient or the wallet's transactions change.
+    fn address_reuse_warning(&self) -> Option<String> {
+        let wallet = self.inner.as_ref()?;
+        let destination =
+            PaymentDestination::parse(&self.send_form.recipient, self.network).ok()?;
+
+        if !paid_before(wallet, &destination.address.script_pubkey()) {
+            return None;
+        }
+
+        Some(match self.contact_paid_at(&destination.address) {
+            Some(contact) => format!(
+                "ADDRESS REUSE: YOU HAVE ALREADY PAID {} AT THIS ADDRESS",
+                contact.name.to_uppercase()
+            ),
+            None => "ADDRESS REUSE: YOU HAVE ALREADY PAID THIS ADDRESS".to_string(),
+        })
+    }
+
+    /// Encrypt the active wallet, along with the application
+    /// settings, and write it to the backup path.
+    pub(crate) fn export_backup(&mut self, settings: &BonsaiSettings) -> Task<WalletMessage> {
+        let Some(data) = self.data.clone() else {
+            self.backup_status = Some(Err(BonsaiWalletError::Generic(
+                "No wallet loaded".to_string(),
+            )));
+            return Task::none();
+        };
+
+        let backup = WalletBackup {
+            network: self.network,
+            wallet: data,
+            settings: settings.clone(),
+        };
+        let path = PathBuf::from(&self.backup_path_input);
+        let passphrase = std::mem::take(&mut self.backup_passphrase_input);
+
+        Task::perform(
+            backup.export(path, passphrase),
+            WalletMessage::BackupExported,
+        )
+    }
+
+    /// Replace the active wallet with the one in a [`WalletBackup`]
+    /// and schedule a rescan from its birthday.
+    ///
+    /// Returns the backup's network and settings, to apply once the wallet is restored.
+    fn restore(
+        &mut self,
+        backup: WalletBackup,
+    ) -> Result<(Network, BonsaiSettings), BonsaiWalletError> {
+        let inner = backup.wallet.create_wallet(backup.network)?;
+        backup.wallet.save(backup.network)?;
+
+        self.network = backup.network;
+        self.inner = Some(inner);
+        self.pending_rescan = Some(backup.wallet.birthday);
+        self.rescan_height = None;
+        if let Some(handle) = self.rescan_handle.take() {
+            handle.abort();
+        }
+        self.activity.clear();
+        self.send_form.reuse_warning = None;
+        self.electrum_index.clear();
+        self.data = Some(backup.wallet);
+        self.publish_scripts();
+
+        Ok((backup.network, backup.settings))
+    }
+
+    /// Start a pending rescan against the embedded node, if there is one.
+    pub(crate) fn start_rescan(
+        &mut self,
+        node_handle: Arc<RwLock<Node>>,
+        token: CancellationToken,
+    ) -> Task<WalletMessage> {
+        match self.pending_rescan.take() {
+            Some(start_height) => {
+                self.rescan_height = Some(start_height);
+                self.rescan_error = None;
+                let scripts = self.scripts.subscribe();
+                let (task, handle) = Task::run(
+                    rescan(node_handle, start_height, scripts, token),
+                    |message| message,
+                )
+                .abortable();
+                self.rescan_handle = Some(handle);
+                task
+            }
+            None => Task::none(),
+        }
+    }
+
+    /// The active wallet's [`Balance`], if a wallet is loaded.
+    pub(crate) fn balance(&self) -> Option<Balance> {
+        self.inner.as_ref().map(|wallet| wallet.balance())
+    }
+
+    /// The height up to which the active wallet is synced, if a wallet is loaded.
+    pub(crate) fn synced_height(&self) -> Option<u32> {
+        self.inner.as_ref().map(|wallet| {
+            self.rescan_height
+                .unwrap_or_else(|| wallet.latest_checkpoint().height())
+        })
+    }
+
+    /// Apply a [`Block`] validated by the embedded node to the active wallet,
+    /// recording and returning the wallet transactions it contains.
+    ///
+    /// A block that doesn't follow a wallet checkpoint is connected to the closest one below it,
+    /// since a rescan skips the blocks without wallet transactions, and the first block it
+    /// applies doesn't follow the genesis checkpoint a loaded or restored wallet starts from.
+    pub(crate) fn apply_block(
+        &mut self,
+        block: &Block,
+        height: u32,
+    ) -> Result<Vec<WalletEvent>, BonsaiWalletError> {
+        let Some(wallet) = self.inner.as_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let below = wallet
+            .latest_checkpoint()
+            .range(..height)
+            .next()
+            .map(|checkpoint| checkpoint.block_id());
+        let applied = match below {
+            Some(below) if below.height + 1 < height => wallet
+                .apply_block_connected_to(block, height, below)
+                .map_err(|e| e.to_string()),
+            _ => wallet.apply_block(block, height).map_err(|e| e.to_string()),
+        };
+        if let Err(e) = applied {
+            return Err(BonsaiWalletError::Chain(format!(
+                "Failed to apply block of height={height} to the wallet: {e}"
+            )));
+        }
+
+        let events = wallet_events(wallet, block, height);
+        self.electrum_index.record_block(wallet, block, height);
+        self.publish_scripts();
+        for event in &events {
+            info!(
+                "Wallet transaction {} seen in block of height={height}",
+                event.txid
+            );
+            // Blocks can be applied more than once, by a rescan and by the node.
+            if !self.activity.iter().any(|e| e.txid == event.txid) {
+                self.activity.push_front(event.clone());
+            }
+        }
+        self.activity.truncate(MAX_ACTIVITY);
+        if !events.is_empty() {
+            self.send_form.reuse_warning = self.address_reuse_warning();
+        }
+
+        Ok(events)
+    }
+
+    /// Move the active wallet's tip to the end of a rescan, past the blocks it skipped.
+    fn connect_tip(&mut self, tip: BlockId) -> Result<(), BonsaiWalletError> {
+        let Some(wallet) = self.inner.as_mut() else {
+            return Ok(());
+        };
+        let latest = wallet.latest_checkpoint();
+        if tip.height <= latest.height() {
+            return Ok(());
+        }
+
+        let chain = latest.push(tip).map_err(|_| {
+            BonsaiWalletError::Chain(format!(
+                "Failed to connect block {} to the wallet",
+                tip.hash
+            ))
+        })?;
+        wallet
+            .apply_update(Update {
+                chain: Some(chain),
+                ..Default::default()
+            })
+            .map_err(|e| BonsaiWalletError::Chain(e.to_string()))
+    }
+
+    /// Publish the active wallet's revealed and lookahead scripts,
+    /// to the rescan and to the Electrum server.
+    fn publish_scripts(&self) {
+        let scripts: Vec<ScriptBuf> = self
+            .inner
+            .as_ref()
+            .map(|wallet| {
+                wallet
+                    .spk_index()
+                    .inner()
+                    .all_spks()
+                    .values()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.electrum_index.watch(&scripts);
+        self.scripts.send_replace(scripts);
     }
 
     pub fn view(&self) -> Element<'_, WalletMessage> {
-        column![
-            text("WALLET").size(24),
-            text("Powered by BDK").size(16),
-            text("Coming Soon").size(16)
-        ]
-        .into()
+        use crate::wallet::view::view_wallet;
+        view_wallet(self)
     }
 }
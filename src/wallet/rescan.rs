@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use bdk_floresta::Node;
+use bdk_wallet::chain::BlockId;
+use bitcoin::ScriptBuf;
+use iced::futures::SinkExt;
+use iced::futures::Stream;
+use iced::futures::channel::mpsc::Sender;
+use tokio::sync::RwLock;
+use tokio::sync::watch;
+use tracing::error;
+use tracing::info;
+
+use crate::node::shutdown::CancellationToken;
+use crate::wallet::error::BonsaiWalletError;
+use crate::wallet::placeholder::WalletMessage;
+
+/// Stream the blocks from `start_height` up to the node's validation height
+/// that may touch the wallet's `scripts`, so they can be applied to the wallet.
+///
+/// The rescan stops, dropping its node handle, when the token is cancelled.
+pub(crate) fn rescan(
+    node_handle: Arc<RwLock<Node>>,
+    start_height: u32,
+    scripts: watch::Receiver<Vec<ScriptBuf>>,
+    token: CancellationToken,
+) -> impl Stream<Item = WalletMessage> {
+    iced::stream::channel(100, move |mut output: Sender<WalletMessage>| async move {
+        let blocks = rescan_blocks(node_handle, start_height, scripts, output.clone());
+        if token.run(blocks).await.is_none() {
+            info!("Wallet rescan cancelled");
+            let _ = output.send(WalletMessage::RescanCancelled).await;
+        }
+    })
+}
+
+/// Blocks are matched against the node's compact block filters, and only fetched if
+/// their filter matches one of the wallet's scripts, or if the node has no filter for them.
+async fn rescan_blocks(
+    node_handle: Arc<RwLock<Node>>,
+    start_height: u32,
+    mut scripts: watch::Receiver<Vec<ScriptBuf>>,
+    mut output: Sender<WalletMessage>,
+) {
+    let tip = {
+        let node = node_handle.read().await;
+        node.get_validation_height()
+            .map_err(|e| e.to_string())
+            .and_then(|height| {
+                node.get_blockhash(height)
+                    .map(|hash| BlockId { height, hash })
+                    .map_err(|e| e.to_string())
+            })
+    };
+    let tip = match tip {
+        Ok(tip) => tip,
+        Err(e) => {
+            let _ = output
+                .send(WalletMessage::RescanFailed(BonsaiWalletError::Chain(e)))
+                .await;
+            return;
+        }
+    };
+
+    info!(
+        "Rescanning wallet from height={start_height} to height={}",
+        tip.height
+    );
+
+    let mut fetched = 0;
+    for height in start_height..=tip.height {
+        let node = node_handle.read().await;
+
+        let blockhash = match node.get_blockhash(height) {
+            Ok(blockhash) => blockhash,
+            Err(e) => {
+                error!("Failed to find a block of height={height} during rescan: {e}");
+                let _ = output
+                    .send(WalletMessage::RescanFailed(BonsaiWalletError::Chain(
+                        e.to_string(),
+                    )))
+                    .await;
+                return;
+            }
+        };
+
+        if let Ok(Some(filter)) = node.get_block_filter(height) {
+            let matched = filter
+                .match_any(
+                    &blockhash,
+                    scripts.borrow_and_update().iter().map(|s| s.as_bytes()),
+                )
+                .unwrap_or(true);
+            if !matched {
+                continue;
+            }
+        }
+
+        match node.get_block(blockhash).await {
+            Ok(Some(block)) => {
+                drop(node);
+                fetched += 1;
+                let _ = output
+                    .send(WalletMessage::RescanBlock(height, Box::new(block)))
+                    .await;
+
+                // Applying the block may reveal new scripts, to match later filters against.
+                if scripts.changed().await.is_err() {
+                    return;
+                }
+            }
+            Ok(None) => {
+                error!("Failed to fetch block of height={height} during rescan: 404 Not Found");
+                let _ = output
+                    .send(WalletMessage::RescanFailed(BonsaiWalletError::Chain(
+                        format!("Block of height={height} not found"),
+                    )))
+                    .await;
+                return;
+            }
+            Err(e) => {
+                error!("Failed to fetch block of height={height} during rescan: {e}");
+                let _ = output
+                    .send(WalletMessage::RescanFailed(BonsaiWalletError::Chain(
+                        e.to_string(),
+                    )))
+                    .await;
+                return;
+            }
+        }
+    }
+
+    info!(
+        "Wallet rescan finished at height={}, after fetching {fetched} blocks",
+        tip.height
+    );
+    let _ = output.send(WalletMessage::RescanComplete(tip)).await;
+}
@@ -0,0 +1,48 @@
+use bitcoin::Amount;
+use bitcoin::Psbt;
+use bitcoin::Script;
+
+use crate::wallet::address_book::PaymentDestination;
+use crate::wallet::error::BonsaiWalletError;
+
+/// Input state for the `SEND` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SendForm {
+    /// An address or BIP21 URI.
+    pub(crate) recipient: String,
+    /// Amount in satoshis.
+    pub(crate) amount: String,
+    /// The last PSBT created, base64-encoded.
+    pub(crate) psbt: Option<String>,
+    pub(crate) error: Option<BonsaiWalletError>,
+    /// Set if the wallet has already paid the recipient.
+    pub(crate) reuse_warning: Option<String>,
+}
+
+/// Create an unsigned [`Psbt`] paying `amount` to a [`PaymentDestination`].
+pub(crate) fn create_psbt(
+    wallet: &mut bdk_wallet::Wallet,
+    destination: &PaymentDestination,
+    amount: Amount,
+) -> Result<Psbt, BonsaiWalletError> {
+    let mut builder = wallet.build_tx();
+    builder.add_recipient(destination.address.script_pubkey(), amount);
+
+    builder
+        .finish()
+        .map_err(|e| BonsaiWalletError::Generic(format!("Failed to create PSBT: {e}")))
+}
+
+/// Whether this wallet has already paid to a `script`.
+pub(crate) fn paid_before(wallet: &bdk_wallet::Wallet, script: &Script) -> bool {
+    wallet.transactions().any(|wallet_tx| {
+        let tx = &wallet_tx.tx_node.tx;
+        let (sent, _received) = wallet.sent_and_received(tx);
+
+        sent > Amount::ZERO
+            && tx
+                .output
+                .iter()
+                .any(|output| output.script_pubkey == *script)
+    })
+}
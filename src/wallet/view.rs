@@ -0,0 +1,544 @@
+use iced::Alignment::Center;
+use iced::Element;
+use iced::Length;
+use iced::Length::Fill;
+use iced::Padding;
+use iced::widget::Container;
+use iced::widget::Space;
+use iced::widget::button;
+use iced::widget::column;
+use iced::widget::container;
+use iced::widget::pick_list;
+use iced::widget::row;
+use iced::widget::scrollable;
+use iced::widget::text;
+use iced::widget::text::Wrapping;
+use iced::widget::text_input;
+
+use crate::common::interface::color::GREEN_SHAMROCK;
+use crate::common::interface::color::ORANGE;
+use crate::common::interface::color::RED;
+use crate::common::interface::container::button_container;
+use crate::common::interface::font::BERKELEY_MONO_BOLD;
+use crate::common::util::format_thousands;
+use crate::node::style::input_field;
+use crate::node::style::table_cell;
+use crate::node::style::title_container;
+use crate::wallet::activity::WalletEvent;
+use crate::wallet::address_book::Contact;
+use crate::wallet::address_book::ContactDestination;
+use crate::wallet::consolidation::format_feerate;
+use crate::wallet::placeholder::Wallet;
+use crate::wallet::placeholder::WalletMessage;
+
+fn table_row<'a>(label: &'a str, value: String) -> Element<'a, WalletMessage> {
+    row![
+        container(text(label).size(14))
+            .padding(10)
+            .width(Length::FillPortion(1))
+            .style(table_cell()),
+        container(text(value).size(14).wrapping(Wrapping::Glyph))
+            .padding(10)
+            .width(Length::FillPortion(2))
+            .style(table_cell()),
+    ]
+    .into()
+}
+
+fn contact_card(idx: usize, contact: &Contact) -> Element<'_, WalletMessage> {
+    let mut card = column![
+        row![
+            text(contact.name.to_uppercase())
+                .size(14)
+                .font(BERKELEY_MONO_BOLD),
+            Space::new().width(Fill),
+            button(text("REMOVE").size(12).color(RED))
+                .on_press(WalletMessage::RemoveContact(idx))
+                .style(button_container())
+                .padding(2),
+        ]
+        .align_y(Center),
+    ]
+    .spacing(5);
+
+    if !contact.notes.is_empty() {
+        card = card.push(text(&contact.notes).size(12).wrapping(Wrapping::Glyph));
+    }
+
+    for destination in &contact.destinations {
+        card = card.push(
+            row![
+                text(destination)
+                    .size(12)
+                    .wrapping(Wrapping::Glyph)
+                    .width(Fill),
+                button(text("PAY").size(12))
+                    .on_press(WalletMessage::PayTo(destination.clone()))
+                    .style(button_container())
+                    .padding(2),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+
+    container(card)
+        .padding(10)
+        .width(Fill)
+        .style(table_cell())
+        .into()
+}
+
+fn activity_row(event: &WalletEvent, tip_height: u32) -> Element<'_, WalletMessage> {
+    let (amount, color) = if event.is_incoming() {
+        (
+            format!("+{} SATS", format_thousands(event.net().to_sat())),
+            GREEN_SHAMROCK,
+        )
+    } else {
+        (
+            format!("{} SATS", format_thousands(event.net().to_sat())),
+            ORANGE,
+        )
+    };
+    let confirmations = event.confirmations(tip_height);
+
+    row![
+        text(amount)
+            .size(12)
+            .color(color)
+            .width(Length::FillPortion(2)),
+        text(format!("BLOCK {}", format_thousands(event.height)))
+            .size(12)
+            .width(Length::FillPortion(2)),
+        text(format!(
+            "{confirmations} CONF{}",
+            if confirmations == 1 { "" } else { "S" }
+        ))
+        .size(12)
+        .width(Length::FillPortion(1)),
+        text(event.txid.to_string())
+            .size(10)
+            .wrapping(Wrapping::None)
+            .width(Length::FillPortion(3)),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+pub(crate) fn view_wallet(wallet: &Wallet) -> Element<'_, WalletMessage> {
+    // Wallet Section.
+    let birthday = wallet
+        .data
+        .as_ref()
+        .map(|d| format_thousands(d.birthday))
+        .unwrap_or("NULL".to_string());
+    let descriptor = wallet
+        .data
+        .as_ref()
+        .map(|d| d.descriptor.clone())
+        .unwrap_or("NULL".to_string());
+    let balance = wallet
+        .balance()
+        .map(|b| format!("{} SATS", format_thousands(b.total().to_sat())))
+        .unwrap_or("NULL".to_string());
+    let synced_height = wallet
+        .synced_height()
+        .map(format_thousands)
+        .unwrap_or("NULL".to_string());
+
+    let wallet_title: Container<'_, WalletMessage> = container(text("WALLET").size(24));
+    let wallet_table = container(column![
+        table_row("NETWORK", wallet.network.to_string().to_uppercase()),
+        table_row("DESCRIPTOR", descriptor),
+        table_row("BIRTHDAY", birthday),
+        table_row("SYNC HEIGHT", synced_height),
+        table_row("BALANCE", balance),
+    ])
+    .style(title_container());
+    let mut wallet_section = column![wallet_title, wallet_table];
+    if let Some(e) = &wallet.rescan_error {
+        wallet_section =
+            wallet_section.push(text(e.to_string().to_uppercase()).size(12).color(RED));
+    }
+
+    // Activity Section.
+    let activity_title: Container<'_, WalletMessage> = container(text("ACTIVITY").size(21));
+    let tip_height = wallet.synced_height().unwrap_or_default();
+    let activity = wallet
+        .activity
+        .iter()
+        .fold(column![].spacing(5), |col, event| {
+            col.push(activity_row(event, tip_height))
+        });
+    let activity = if wallet.activity.is_empty() {
+        column![text("NO ACTIVITY").size(12)]
+    } else {
+        activity
+    };
+    let activity_section = column![
+        activity_title,
+        container(scrollable(activity).height(Length::Fixed(150.0)))
+            .style(title_container())
+            .padding(10)
+    ];
+
+    // Send Section.
+    let contact_destinations: Vec<ContactDestination> = wallet
+        .contacts()
+        .iter()
+        .flat_map(Contact::contact_destinations)
+        .collect();
+    let selected_destination = contact_destinations
+        .iter()
+        .find(|cd| cd.destination == wallet.send_form.recipient)
+        .cloned();
+
+    let send_title: Container<'_, WalletMessage> = container(text("SEND").size(21));
+    let mut send_controls = column![
+        pick_list(
+            contact_destinations,
+            selected_destination,
+            |cd: ContactDestination| WalletMessage::PayTo(cd.destination),
+        )
+        .placeholder("PAY A CONTACT")
+        .text_size(12)
+        .width(Fill),
+        text_input("ADDRESS OR BIP21 URI", &wallet.send_form.recipient)
+            .on_input(WalletMessage::SendRecipientInputChanged)
+            .style(input_field())
+            .size(12)
+            .padding(10),
+        row![
+            text_input("AMOUNT [SATS]", &wallet.send_form.amount)
+                .on_input(WalletMessage::SendAmountInputChanged)
+                .style(input_field())
+                .size(12)
+                .padding(10),
+            button(text("CREATE PSBT").size(12))
+                .on_press_maybe(
+                    if wallet.inner.is_some()
+                        && !wallet.send_form.recipient.is_empty()
+                        && !wallet.send_form.amount.is_empty()
+                    {
+                        Some(WalletMessage::CreatePsbt)
+                    } else {
+                        None
+                    }
+                )
+                .style(button_container())
+                .padding(10),
+        ]
+        .spacing(10)
+        .align_y(Center),
+    ]
+    .spacing(10);
+
+    if let Some(label) = wallet.recipient_label() {
+        send_controls = send_controls.push(text(format!("LABEL: {label}")).size(12));
+    }
+    if let Some(warning) = &wallet.send_form.reuse_warning {
+        send_controls = send_controls.push(text(warning).size(12).color(ORANGE));
+    }
+    if let Some(e) = &wallet.send_form.error {
+        send_controls = send_controls.push(text(e.to_string().to_uppercase()).size(12).color(RED));
+    }
+    if let Some(psbt) = &wallet.send_form.psbt {
+        send_controls = send_controls.push(
+            row![
+                text(psbt).size(10).wrapping(Wrapping::Glyph).width(Fill),
+                button(text("COPY").size(12))
+                    .on_press(WalletMessage::CopyPsbt)
+                    .style(button_container())
+                    .padding(2),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+
+    let send_section = column![
+        send_title,
+        container(send_controls)
+            .style(title_container())
+            .padding(10)
+    ];
+
+    // Consolidation Section.
+    let consolidation_title: Container<'_, WalletMessage> = container(text("CONSOLIDATE").size(21));
+    let mut consolidation_controls = column![
+        row![
+            text_input(
+                "SMALL UTXO THRESHOLD [SATS]",
+                &wallet.consolidation_form.threshold
+            )
+            .on_input(WalletMessage::ConsolidationThresholdInputChanged)
+            .style(input_field())
+            .size(12)
+            .padding(10),
+            button(text("PLAN").size(12))
+                .on_press_maybe(if wallet.inner.is_some() {
+                    Some(WalletMessage::PlanConsolidation)
+                } else {
+                    None
+                })
+                .style(button_container())
+                .padding(10),
+        ]
+        .spacing(10)
+        .align_y(Center),
+    ]
+    .spacing(10);
+
+    if let Some(plan) = &wallet.consolidation_form.plan {
+        let savings = if plan.savings.is_negative() {
+            format!(
+                "-{} SATS",
+                format_thousands(plan.savings.unsigned_abs().to_sat())
+            )
+        } else {
+            format!("{} SATS", format_thousands(plan.savings.to_sat()))
+        };
+        let (recommendation, recommendation_color) = if plan.is_recommended() {
+            ("FEES ARE LOW, CONSOLIDATING SAVES FEES", GREEN_SHAMROCK)
+        } else if !plan.fees_are_low {
+            ("FEES ARE NOT LOW, CONSIDER WAITING", ORANGE)
+        } else {
+            ("CONSOLIDATING COSTS MORE THAN IT SAVES", ORANGE)
+        };
+
+        consolidation_controls = consolidation_controls.push(column![
+            table_row(
+                "UTXOS",
+                format!(
+                    "{} ({} SATS)",
+                    plan.outpoints.len(),
+                    format_thousands(plan.total.to_sat())
+                )
+            ),
+            table_row(
+                "FEERATE",
+                format!(
+                    "{} (TYPICAL {})",
+                    format_feerate(plan.feerate),
+                    format_feerate(plan.future_feerate)
+                )
+            ),
+            table_row(
+                "FEE",
+                format!("{} SATS", format_thousands(plan.fee.to_sat()))
+            ),
+            table_row("FUTURE SAVINGS", savings),
+            table_row(
+                "PRIVACY COST",
+                format!("LINKS {} ADDRESSES ON-CHAIN", plan.addresses_linked)
+            ),
+        ]);
+        consolidation_controls = consolidation_controls.push(
+            row![
+                text(recommendation)
+                    .size(12)
+                    .color(recommendation_color)
+                    .width(Fill),
+                button(text("CREATE PSBT").size(12))
+                    .on_press(WalletMessage::CreateConsolidationPsbt)
+                    .style(button_container())
+                    .padding(10),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+    if let Some(e) = &wallet.consolidation_form.error {
+        consolidation_controls =
+            consolidation_controls.push(text(e.to_string().to_uppercase()).size(12).color(RED));
+    }
+    if let Some(psbt) = &wallet.consolidation_form.psbt {
+        consolidation_controls = consolidation_controls.push(
+            row![
+                text(psbt).size(10).wrapping(Wrapping::Glyph).width(Fill),
+                button(text("COPY").size(12))
+                    .on_press(WalletMessage::CopyConsolidationPsbt)
+                    .style(button_container())
+                    .padding(2),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+
+    let consolidation_section = column![
+        consolidation_title,
+        container(consolidation_controls)
+            .style(title_container())
+            .padding(10)
+    ];
+
+    // Backup Section.
+    let export_title: Container<'_, WalletMessage> = container(text("EXPORT BACKUP").size(21));
+    let export_controls = container(
+        column![
+            text_input("BACKUP PATH", &wallet.backup_path_input)
+                .on_input(WalletMessage::BackupPathInputChanged)
+                .style(input_field())
+                .size(12)
+                .padding(10),
+            row![
+                text_input("PASSPHRASE", &wallet.backup_passphrase_input)
+                    .on_input(WalletMessage::BackupPassphraseInputChanged)
+                    .secure(true)
+                    .style(input_field())
+                    .size(12)
+                    .padding(10),
+                button(text("EXPORT").size(12))
+                    .on_press_maybe(
+                        if wallet.data.is_some() && !wallet.backup_passphrase_input.is_empty() {
+                            Some(WalletMessage::ExportBackup)
+                        } else {
+                            None
+                        }
+                    )
+                    .style(button_container())
+                    .padding(10),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let export_section = column![export_title, export_controls];
+
+    let restore_title: Container<'_, WalletMessage> = container(text("RESTORE BACKUP").size(21));
+    let restore_controls = container(
+        column![
+            text_input("BACKUP PATH", &wallet.restore_path_input)
+                .on_input(WalletMessage::RestorePathInputChanged)
+                .style(input_field())
+                .size(12)
+                .padding(10),
+            row![
+                text_input("PASSPHRASE", &wallet.restore_passphrase_input)
+                    .on_input(WalletMessage::RestorePassphraseInputChanged)
+                    .secure(true)
+                    .style(input_field())
+                    .size(12)
+                    .padding(10),
+                button(text("RESTORE").size(12))
+                    .on_press_maybe(if !wallet.restore_passphrase_input.is_empty() {
+                        Some(WalletMessage::RestoreBackup)
+                    } else {
+                        None
+                    })
+                    .style(button_container())
+                    .padding(10),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .spacing(10),
+    )
+    .style(title_container())
+    .padding(10);
+    let restore_section = column![restore_title, restore_controls];
+
+    let backup_status = match &wallet.backup_status {
+        Some(Ok(status)) => text(status.clone()).size(12).color(GREEN_SHAMROCK),
+        Some(Err(e)) => text(e.to_string().to_uppercase()).size(12).color(RED),
+        None => text("").size(12),
+    };
+
+    let left = scrollable(
+        column![
+            wallet_section,
+            activity_section,
+            send_section,
+            consolidation_section,
+            export_section,
+            restore_section,
+            backup_status.wrapping(Wrapping::Glyph),
+        ]
+        .spacing(15)
+        .padding(Padding {
+            top: 0.0,
+            right: 10.0,
+            bottom: 0.0,
+            left: 0.0,
+        }),
+    )
+    .height(Fill)
+    .width(Length::FillPortion(1));
+
+    // Address Book Section.
+    let address_book_title: Container<'_, WalletMessage> = container(text("ADDRESS BOOK").size(21));
+    let contacts = wallet
+        .contacts()
+        .iter()
+        .enumerate()
+        .fold(column![].spacing(5), |col, (idx, contact)| {
+            col.push(contact_card(idx, contact))
+        });
+    let contacts = if wallet.contacts().is_empty() {
+        column![text("NO CONTACTS").size(12)]
+    } else {
+        contacts
+    };
+    let address_book = container(scrollable(contacts).height(Fill))
+        .style(title_container())
+        .padding(10)
+        .height(Fill);
+    let address_book_section = column![address_book_title, address_book].height(Fill);
+
+    let add_contact_title: Container<'_, WalletMessage> = container(text("ADD CONTACT").size(21));
+    let mut add_contact_controls = column![
+        text_input("NAME", &wallet.contact_form.name)
+            .on_input(WalletMessage::ContactNameInputChanged)
+            .style(input_field())
+            .size(12)
+            .padding(10),
+        text_input(
+            "ADDRESSES OR BIP21 URIS, COMMA-SEPARATED",
+            &wallet.contact_form.destinations
+        )
+        .on_input(WalletMessage::ContactDestinationsInputChanged)
+        .style(input_field())
+        .size(12)
+        .padding(10),
+        row![
+            text_input("NOTES", &wallet.contact_form.notes)
+                .on_input(WalletMessage::ContactNotesInputChanged)
+                .style(input_field())
+                .size(12)
+                .padding(10),
+            button(text("ADD").size(12))
+                .on_press_maybe(if wallet.data.is_some() {
+                    Some(WalletMessage::AddContact)
+                } else {
+                    None
+                })
+                .style(button_container())
+                .padding(10),
+        ]
+        .spacing(10)
+        .align_y(Center),
+    ]
+    .spacing(10);
+    if let Some(e) = &wallet.contact_error {
+        add_contact_controls =
+            add_contact_controls.push(text(e.to_string().to_uppercase()).size(12).color(RED));
+    }
+    let add_contact_section = column![
+        add_contact_title,
+        container(add_contact_controls)
+            .style(title_container())
+            .padding(10)
+    ];
+
+    let right = column![address_book_section, add_contact_section]
+        .spacing(15)
+        .width(Length::FillPortion(1));
+
+    row![left, right].spacing(20).into()
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use bitcoin::Network;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::error;
+
+use crate::settings::bonsai_settings::BonsaiSettings;
+use crate::wallet::address_book::Contact;
+use crate::wallet::error::BonsaiWalletError;
+
+pub(crate) const WALLET_FILE: &str = "wallet.toml";
+
+/// Everything needed to rebuild a wallet from scratch,
+/// persisted under the network's data directory.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct WalletData {
+    /// The external descriptor.
+    pub(crate) descriptor: String,
+    /// The internal (change) descriptor.
+    pub(crate) change_descriptor: String,
+    /// The height from which the wallet has to be scanned.
+    pub(crate) birthday: u32,
+    /// Labels, keyed by address or txid.
+    #[serde(default)]
+    pub(crate) labels: BTreeMap<String, String>,
+    /// Address book contacts.
+    #[serde(default)]
+    pub(crate) address_book: Vec<Contact>,
+}
+
+impl WalletData {
+    /// Path to the wallet file for a given [`Network`].
+    pub(crate) fn path(network: Network) -> PathBuf {
+        BonsaiSettings::base_dir()
+            .join(network.to_string())
+            .join(WALLET_FILE)
+    }
+
+    /// Load the wallet data for a given [`Network`], if it exists.
+    pub(crate) fn load(network: Network) -> Option<Self> {
+        let path = Self::path(network);
+
+        if !path.exists() {
+            return None;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => match toml::from_str(&contents) {
+                Ok(data) => Some(data),
+                Err(e) => {
+                    error!(
+                        "Failed to parse wallet file at {}: {}",
+                        path.to_string_lossy(),
+                        e
+                    );
+                    None
+                }
+            },
+            Err(e) => {
+                error!(
+                    "Failed to read wallet file at {}: {}",
+                    path.to_string_lossy(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Save the wallet data for a given [`Network`] to disk.
+    pub(crate) fn save(&self, network: Network) -> Result<(), BonsaiWalletError> {
+        let path = Self::path(network);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let wallet_toml = toml::to_string_pretty(self)
+            .map_err(|e| BonsaiWalletError::Serialization(e.to_string()))?;
+        fs::write(&path, wallet_toml)?;
+
+        Ok(())
+    }
+
+    /// Build a [`bdk_wallet::Wallet`] from the descriptors.
+    pub(crate) fn create_wallet(
+        &self,
+        network: Network,
+    ) -> Result<bdk_wallet::Wallet, BonsaiWalletError> {
+        bdk_wallet::Wallet::create(self.descriptor.clone(), self.change_descriptor.clone())
+            .network(network)
+            .create_wallet_no_persist()
+            .map_err(|e| BonsaiWalletError::Descriptor(e.to_string()))
+    }
+}